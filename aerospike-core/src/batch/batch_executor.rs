@@ -15,10 +15,14 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use futures::Future;
 
 use crate::batch::BatchRead;
 use crate::cluster::partition::Partition;
 use crate::cluster::{Cluster, Node};
+use crate::commands::single_command::try_hedged;
 use crate::commands::BatchReadCommand;
 use crate::errors::Result;
 use crate::policy::{BatchPolicy, Concurrency};
@@ -48,7 +52,10 @@ impl BatchExecutor {
                 jobs.push( BatchReadCommand::new(policy, node.clone(), node_chunk.to_vec()) );
             }
         }
-        let reads = self.execute_batch_jobs(jobs, policy.concurrency).await?;
+        let hedge_delay = policy.replica.hedge_delay(policy.hedge_delay);
+        let reads = self
+            .execute_batch_jobs(jobs, policy.concurrency, hedge_delay)
+            .await?;
         let mut all_results: Vec<_> = reads.into_iter().flat_map(|cmd|cmd.batch_reads).collect();
         all_results.sort_by_key(|(_, i)|*i);
         Ok(all_results.into_iter().map(|(b, _)|b).collect())
@@ -58,14 +65,68 @@ impl BatchExecutor {
         &self,
         jobs: Vec<BatchReadCommand>,
         concurrency: Concurrency,
+        hedge_delay: Option<Duration>,
     ) -> Result<Vec<BatchReadCommand>> {
-        let handles = jobs.into_iter().map(|job|job.execute(self.cluster.clone()));
+        let handles = jobs
+            .into_iter()
+            .map(|job| Self::execute_hedged(self.cluster.clone(), job, hedge_delay));
         match concurrency {
             Concurrency::Sequential => futures::future::join_all(handles).await.into_iter().collect(),
             Concurrency::Parallel => futures::future::join_all(handles.map(aerospike_rt::spawn)).await.into_iter().map(|value|value.map_err(|e|e.to_string())?).collect(),
         }
     }
 
+    async fn execute_hedged(
+        cluster: Arc<Cluster>,
+        job: BatchReadCommand,
+        hedge_delay: Option<Duration>,
+    ) -> Result<BatchReadCommand> {
+        let hedge = job.clone();
+        try_hedged(hedge_delay, job.execute(cluster.clone()), || {
+            Self::execute_on_next_replicas(cluster, hedge)
+        })
+        .await
+    }
+
+    // Sends the keys of a batch job to the next replica of each key, regrouped by node, and
+    // merges the results back into a single job. Returns `None` if a key has no replica other
+    // than the node of the job, e.g. with a replication factor of 1.
+    fn execute_on_next_replicas(
+        cluster: Arc<Cluster>,
+        job: BatchReadCommand,
+    ) -> Option<impl Future<Output = Result<BatchReadCommand>>> {
+        let replica = job.policy().replica;
+        let mut map: HashMap<Arc<Node>, Vec<(BatchRead, usize)>> = HashMap::new();
+        for batch_read in &job.batch_reads {
+            let partition = Partition::new_by_key(&batch_read.0.key);
+            let node = cluster
+                .get_node(&partition, replica, Arc::downgrade(&job.node))
+                .ok()?;
+            if Arc::ptr_eq(&node, &job.node) {
+                return None;
+            }
+            map.entry(node)
+                .or_insert_with(Vec::new)
+                .push(batch_read.clone());
+        }
+
+        Some(async move {
+            let handles = map.into_iter().map(|(node, batch_reads)| {
+                BatchReadCommand::new(job.policy(), node, batch_reads).execute(cluster.clone())
+            });
+            let results = futures::future::try_join_all(handles).await?;
+            let batch_reads = results
+                .into_iter()
+                .flat_map(|cmd| cmd.batch_reads)
+                .collect();
+            Ok(BatchReadCommand::new(
+                job.policy(),
+                job.node.clone(),
+                batch_reads,
+            ))
+        })
+    }
+
     fn get_batch_nodes(
         &self,
         batch_reads: &[BatchRead],
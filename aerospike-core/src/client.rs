@@ -13,32 +13,44 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::collections::HashMap;
+use std::fmt::Write;
 use std::path::Path;
 use std::str;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::vec::Vec;
 
 use crate::batch::BatchExecutor;
 use crate::cluster::{Cluster, Node};
 use crate::commands::operate_command::OperateRecord;
+use crate::commands::single_command::try_hedged;
+use crate::commands::txn_command;
 use crate::commands::{
     DeleteCommand, ExecuteUDFCommand, ExistsCommand, OperateCommand, QueryCommand, ReadCommand,
     ScanCommand, TouchCommand, WriteCommand,
 };
-use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
+use crate::info;
 use crate::net::ToHosts;
+use crate::operations::cdt_context::CdtContext;
 use crate::operations::{Operation, OperationType};
-use crate::policy::{BatchPolicy, ClientPolicy, QueryPolicy, ReadPolicy, ScanPolicy, WritePolicy};
+use crate::policy::{
+    BasePolicy, BatchPolicy, ClientPolicy, GenerationPolicy, QueryPolicy, ReadPolicy,
+    RecordExistsAction, ScanPolicy, UpdatePolicy, WritePolicy,
+};
+use crate::query::Aggregator;
 use crate::task::{IndexTask, RegisterTask};
 use crate::{
-    BatchRead, Bin, Bins, CollectionIndexType, IndexType, Key, Record, Recordset, ResultCode,
-    Statement, UDFLang, Value,
+    AbortStatus, BatchRead, Bin, Bins, CollectionIndexType, CommitStatus, IndexInfo, IndexType,
+    Key, NamespaceInfo, Record, RecordRef, Recordset, ResultCode, SetInfo, Statement, Txn, UDFInfo,
+    UDFLang, Update, Value,
 };
 use aerospike_rt::fs::File;
 #[cfg(all(any(feature = "rt-tokio"), not(feature = "rt-async-std")))]
 use aerospike_rt::io::AsyncReadExt;
 #[cfg(all(any(feature = "rt-async-std"), not(feature = "rt-tokio")))]
 use futures::AsyncReadExt;
+use sha1::{Digest, Sha1};
 
 /// Instantiate a Client instance to access an Aerospike database cluster and perform database
 /// operations.
@@ -183,10 +195,109 @@ impl Client {
     where
         T: Into<Bins> + Send + Sync + 'static,
     {
+        async fn read(mut command: ReadCommand<'_>) -> Result<Record> {
+            command.execute().await?;
+            Ok(command.record.unwrap())
+        }
+
         let bins = bins.into();
-        let mut command = ReadCommand::new(&policy.base_policy, self.cluster.clone(), key, bins, policy.replica);
-        command.execute().await?;
-        Ok(command.record.unwrap())
+        let command = || {
+            ReadCommand::new(
+                &policy.base_policy,
+                self.cluster.clone(),
+                key,
+                bins.clone(),
+                policy.replica,
+            )
+        };
+        let hedge = || {
+            let mut command = command();
+            command
+                .single_command
+                .skip_preferred_node()
+                .then(|| read(command))
+        };
+        let hedge_delay = policy.replica.hedge_delay(policy.hedge_delay);
+        try_hedged(hedge_delay, read(command()), hedge).await
+    }
+
+    /// Read record for the specified key and pass it to `f` while it is still held in the receive
+    /// buffer of the connection, without decoding it into an owned `Record` first. Bins are
+    /// decoded only when accessed through the `RecordRef`, and string and blob values borrow the
+    /// received bytes, avoiding allocations for large values. Values that need to outlive the
+    /// closure have to be copied, e.g. using `ValueRef::to_value` or `RecordRef::to_record`.
+    /// Returns the result of `f`.
+    ///
+    /// # Examples
+    ///
+    /// Compute a checksum of a blob bin without copying it.
+    ///
+    /// ```rust,edition2018
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).await.unwrap();
+    /// let key = as_key!("test", "test", "mykey");
+    /// let checksum = client
+    ///     .get_with(&ReadPolicy::default(), &key, ["payload"], |record| {
+    ///         match record.bin("payload")? {
+    ///             Some(ValueRef::Blob(bytes)) => Ok(bytes.iter().map(|b| u64::from(*b)).sum()),
+    ///             _ => Ok(0u64),
+    ///         }
+    ///     })
+    ///     .await;
+    /// match checksum {
+    ///     Ok(Ok(checksum)) => println!("Checksum: {}", checksum),
+    ///     Ok(Err(err)) | Err(err) => println!("Error fetching record: {}", err),
+    /// }
+    /// ```
+    pub async fn get_with<T, F, R>(
+        &self,
+        policy: &ReadPolicy,
+        key: &Key,
+        bins: T,
+        f: F,
+    ) -> Result<R>
+    where
+        T: Into<Bins> + Send + Sync + 'static,
+        F: FnOnce(RecordRef<'_>) -> R + Send,
+        R: Send,
+    {
+        async fn read(mut command: ReadCommand<'_>) -> Result<()> {
+            command.execute().await
+        }
+
+        let bins = bins.into();
+        // With hedged reads, the record may be received twice; `f` is only called for the first.
+        let state = Mutex::new((Some(f), None));
+        let visitor = |record: RecordRef<'_>| {
+            let mut state = state.lock().unwrap();
+            if let Some(f) = state.0.take() {
+                state.1 = Some(f(record));
+            }
+        };
+        let command = || {
+            ReadCommand::new(
+                &policy.base_policy,
+                self.cluster.clone(),
+                key,
+                bins.clone(),
+                policy.replica,
+            )
+            .with_visitor(&visitor)
+        };
+        let hedge = || {
+            let mut command = command();
+            command
+                .single_command
+                .skip_preferred_node()
+                .then(|| read(command))
+        };
+        let hedge_delay = policy.replica.hedge_delay(policy.hedge_delay);
+        try_hedged(hedge_delay, read(command()), hedge).await?;
+
+        let result = state.into_inner().unwrap().1;
+        result.ok_or_else(|| ErrorKind::BadResponse("Record was not received".to_string()).into())
     }
 
     /// Read multiple record for specified batch keys in one batch call. This method allows
@@ -228,6 +339,7 @@ impl Client {
         policy: &BatchPolicy,
         batch_reads: Vec<BatchRead>,
     ) -> Result<Vec<BatchRead>> {
+        check_no_txn(&policy.base_policy, "batch")?;
         let executor = BatchExecutor::new(self.cluster.clone());
         executor.execute_batch_read(policy, batch_reads).await
     }
@@ -407,11 +519,43 @@ impl Client {
 
     /// Determine if a record key exists. The policy can be used to specify timeouts.
     pub async fn exists(&self, policy: &WritePolicy, key: &Key) -> Result<bool> {
-        let mut command = ExistsCommand::new(policy, self.cluster.clone(), key);
+        let mut command = ExistsCommand::new(
+            &policy.base_policy,
+            self.cluster.clone(),
+            key,
+            crate::policy::Replica::Master,
+        );
         command.execute().await?;
         Ok(command.exists)
     }
 
+    /// Determine if a record key exists, using a read policy. Unlike `exists`, the policy can be
+    /// used to specify the replica to read from and to request hedging.
+    pub async fn exists_with_read_policy(&self, policy: &ReadPolicy, key: &Key) -> Result<bool> {
+        async fn exists(mut command: ExistsCommand<'_>) -> Result<bool> {
+            command.execute().await?;
+            Ok(command.exists)
+        }
+
+        let command = || {
+            ExistsCommand::new(
+                &policy.base_policy,
+                self.cluster.clone(),
+                key,
+                policy.replica,
+            )
+        };
+        let hedge = || {
+            let mut command = command();
+            command
+                .single_command
+                .skip_preferred_node()
+                .then(|| exists(command))
+        };
+        let hedge_delay = policy.replica.hedge_delay(policy.hedge_delay);
+        try_hedged(hedge_delay, exists(command()), hedge).await
+    }
+
     /// Perform multiple read/write operations on a single key in one batch call.
     ///
     /// Operations on scalar values, lists and maps can be performed in the same call.
@@ -452,6 +596,176 @@ impl Client {
         Ok(command.record)
     }
 
+    /// Read a record, modify it and write it back, using the record generation to detect
+    /// concurrent modifications. `f` is called with the current record, or `None` if the record
+    /// does not exist, and returns the modification to apply. If the record was modified, created
+    /// or deleted by another client in the meantime, the cycle is retried with the new record,
+    /// sleeping between retries as configured in the policy; `f` is therefore called once per
+    /// attempt and must not have side effects beyond computing the update.
+    ///
+    /// Existing records are written with `GenerationPolicy::ExpectGenEqual` and the generation of
+    /// the record that was read; new records are written with `RecordExistsAction::CreateOnly`.
+    /// All other fields of the policy's write policy, e.g. the expiration, apply unchanged.
+    ///
+    /// Returns whether the record was written or deleted; `false` if `f` returned
+    /// `Update::Abort`, or `Update::Delete` for a record that does not exist. Fails with
+    /// `ResultCode::GenerationError` or `ResultCode::KeyExistsError` if the record was still
+    /// modified concurrently after the last retry.
+    ///
+    /// # Examples
+    ///
+    /// Mark an order as shipped, unless it has not been paid yet.
+    ///
+    /// ```rust,edition2018
+    /// # use aerospike::*;
+    /// use std::collections::HashMap;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).await.unwrap();
+    /// let key = as_key!("test", "orders", "order-1");
+    /// let result = client
+    ///     .update_with(&UpdatePolicy::default(), &key, |record| match record {
+    ///         Some(record) if record.bins.get("status") == Some(&Value::from("paid")) => {
+    ///             let mut bins = HashMap::new();
+    ///             bins.insert("status".to_string(), Value::from("shipped"));
+    ///             Update::Put(bins)
+    ///         }
+    ///         _ => Update::Abort,
+    ///     })
+    ///     .await;
+    /// match result {
+    ///     Ok(true) => println!("Order shipped"),
+    ///     Ok(false) => println!("Order not paid"),
+    ///     Err(err) => println!("Error updating order: {}", err),
+    /// }
+    /// ```
+    pub async fn update_with<F>(&self, policy: &UpdatePolicy, key: &Key, mut f: F) -> Result<bool>
+    where
+        F: FnMut(Option<Record>) -> Update + Send,
+    {
+        let read_policy = ReadPolicy {
+            base_policy: policy.write_policy.base_policy.clone(),
+            ..ReadPolicy::default()
+        };
+        let mut retry = 0;
+        loop {
+            let record = match self.get(&read_policy, key, Bins::All).await {
+                Ok(record) => Some(record),
+                Err(Error(ErrorKind::ServerError(ResultCode::KeyNotFoundError), _)) => None,
+                Err(err) => return Err(err),
+            };
+
+            let mut write_policy = policy.write_policy.clone();
+            if let Some(ref record) = record {
+                write_policy.generation_policy = GenerationPolicy::ExpectGenEqual;
+                write_policy.generation = record.generation;
+            } else {
+                write_policy.generation_policy = GenerationPolicy::None;
+                write_policy.record_exists_action = RecordExistsAction::CreateOnly;
+            }
+            let exists = record.is_some();
+
+            let result = match f(record) {
+                Update::Abort => return Ok(false),
+                Update::Delete if !exists => return Ok(false),
+                Update::Delete => match self.delete(&write_policy, key).await {
+                    Ok(true) => Ok(()),
+                    // The record was deleted concurrently.
+                    Ok(false) => Err(ErrorKind::ServerError(ResultCode::KeyNotFoundError).into()),
+                    Err(err) => Err(err),
+                },
+                Update::Put(bins) => {
+                    let (names, values): (Vec<String>, Vec<Value>) = bins.into_iter().unzip();
+                    let bins: Vec<Bin> = names
+                        .iter()
+                        .zip(values)
+                        .map(|(name, value)| Bin::new(name, value))
+                        .collect();
+                    self.put(&write_policy, key, &bins).await
+                }
+            };
+
+            match result {
+                Ok(()) => return Ok(true),
+                Err(Error(ErrorKind::ServerError(rc), _))
+                    if retry < policy.max_retries
+                        && matches!(
+                            rc,
+                            ResultCode::GenerationError
+                                | ResultCode::KeyExistsError
+                                | ResultCode::KeyNotFoundError
+                        ) => {}
+                Err(err) => return Err(err),
+            }
+
+            retry += 1;
+            if let Some(sleep) = policy.backoff(retry) {
+                aerospike_rt::sleep(sleep).await;
+            }
+        }
+    }
+
+    /// Start a multi-record transaction. Commands take part in the transaction when the returned
+    /// handle is set as the `txn` of their policy. The transaction must be completed with
+    /// `commit` or `abort`. Transactions require a strong consistency namespace.
+    ///
+    /// # Examples
+    ///
+    /// Transfer an amount between two accounts.
+    ///
+    /// ```rust,edition2018
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).await.unwrap();
+    /// let txn = client.begin_transaction();
+    /// let mut policy = WritePolicy::default();
+    /// policy.base_policy.txn = Some(txn.clone());
+    ///
+    /// let from = as_key!("test", "accounts", "alice");
+    /// let to = as_key!("test", "accounts", "bob");
+    /// let res = async {
+    ///     client.add(&policy, &from, &[as_bin!("balance", -100)]).await?;
+    ///     client.add(&policy, &to, &[as_bin!("balance", 100)]).await
+    /// }
+    /// .await;
+    ///
+    /// match res {
+    ///     Ok(()) => match client.commit(&WritePolicy::default(), &txn).await {
+    ///         Ok(status) => println!("Transfer committed: {:?}", status),
+    ///         Err(err) => println!("Transfer failed: {}", err),
+    ///     },
+    ///     Err(err) => {
+    ///         println!("Transfer failed: {}", err);
+    ///         client.abort(&WritePolicy::default(), &txn).await.unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn begin_transaction(&self) -> Txn {
+        Txn::new()
+    }
+
+    /// Commit a multi-record transaction. The versions of all records read in the transaction
+    /// are verified first; if any of them was modified by another command, the transaction is
+    /// aborted and `ErrorKind::TxnCommit` with `CommitError::VerifyFail` is returned. Otherwise
+    /// the records written in the transaction are rolled forward. The policy applies to all
+    /// commands sent during the commit.
+    ///
+    /// If `ErrorKind::TxnCommit` reports the commit as in doubt, it is unknown whether the
+    /// transaction was committed; the commit can then be retried. A failure to roll forward some
+    /// records or to remove the monitor record after the commit point is reported in the returned
+    /// status; the server completes those steps once the transaction deadline has passed.
+    pub async fn commit(&self, policy: &WritePolicy, txn: &Txn) -> Result<CommitStatus> {
+        txn_command::commit(policy, &self.cluster, txn).await
+    }
+
+    /// Abort a multi-record transaction and roll back the records written in it. Fails with
+    /// `ResultCode::MrtCommitted` if the transaction was already committed. The policy applies to
+    /// all commands sent during the abort.
+    pub async fn abort(&self, policy: &WritePolicy, txn: &Txn) -> Result<AbortStatus> {
+        txn_command::abort(policy, &self.cluster, txn).await
+    }
+
     /// Register a package containing user-defined functions (UDF) with the cluster. This
     /// asynchronous server call will return before the command is complete. The client registers
     /// the UDF package with a single, random cluster node; from there a copy will get distributed
@@ -550,6 +864,74 @@ impl Client {
         self.register_udf(&udf_body, udf_name, language).await
     }
 
+    /// Register a package containing user-defined functions (UDF) with the cluster, unless the
+    /// cluster already has a package with the same name and content. The SHA-1 hash of the local
+    /// file is compared to the hash reported by the cluster. Returns `None` if the package was
+    /// up to date and did not have to be registered.
+    pub async fn register_udf_if_changed(
+        &self,
+        client_path: &str,
+        udf_name: &str,
+        language: UDFLang,
+    ) -> Result<Option<RegisterTask>> {
+        let path = Path::new(client_path);
+        let mut file = File::open(&path).await?;
+        let mut udf_body: Vec<u8> = vec![];
+        file.read_to_end(&mut udf_body).await?;
+
+        let hash = Sha1::digest(&udf_body)
+            .iter()
+            .fold(String::new(), |mut hash, byte| {
+                let _ = write!(hash, "{:02x}", byte);
+                hash
+            });
+        let udfs = self.list_udfs().await?;
+        if udfs
+            .iter()
+            .any(|udf| udf.package_name == udf_name && udf.hash == hash)
+        {
+            return Ok(None);
+        }
+
+        self.register_udf(&udf_body, udf_name, language)
+            .await
+            .map(Some)
+    }
+
+    /// List the user-defined function (UDF) packages registered with the cluster.
+    pub async fn list_udfs(&self) -> Result<Vec<UDFInfo>> {
+        let cmd = "udf-list";
+        let node = self.cluster.get_random_node()?;
+        let response = node.info(&[cmd]).await?;
+
+        match response.get(cmd) {
+            Some(list) => UDFInfo::parse_list(list),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Download the source of a user-defined function (UDF) package from the cluster. The
+    /// package is identified by its file name, e.g. `example.lua`.
+    pub async fn get_udf(&self, udf_name: &str) -> Result<Vec<u8>> {
+        let cmd = format!("udf-get:filename={};", udf_name);
+        let node = self.cluster.get_random_node()?;
+        // Sample response: {"udf-get:filename=file_name.lua;": "gen=...;type=LUA;content=..."}
+        let response = node.info(&[&cmd]).await?;
+
+        let fields: HashMap<&str, &str> = response
+            .get(&cmd)
+            .map(|resp| {
+                resp.split(';')
+                    .filter_map(|field| field.split_once('='))
+                    .collect()
+            })
+            .unwrap_or_default();
+        match fields.get("content") {
+            Some(content) => Ok(base64::decode(content)?),
+            None => bail!("UDF Get failed: {:?}", response),
+        }
+    }
+
     /// Remove a user-defined function (UDF) module from the server.
     pub async fn remove_udf(&self, udf_name: &str, language: UDFLang) -> Result<()> {
         let cmd = format!("udf-remove:filename={}.{};", udf_name, language);
@@ -646,12 +1028,14 @@ impl Client {
     where
         T: Into<Bins> + Send + Sync + 'static,
     {
+        check_no_txn(&policy.base_policy, "scan")?;
         let bins = bins.into();
-        let nodes = self.cluster.nodes();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, nodes.len()));
-        for node in nodes {
-            let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
-            let node = node.clone();
+        let node_partitions = self.cluster.partitions_by_node(namespace, policy.replica);
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            node_partitions.len(),
+        ));
+        for (node, partitions) in node_partitions {
             let recordset = recordset.clone();
             let policy = policy.clone();
             let namespace = namespace.to_owned();
@@ -669,6 +1053,55 @@ impl Client {
         Ok(recordset)
     }
 
+    /// Read all records in the specified namespace and set and aggregate them on the client. The
+    /// records of each node are folded into a partial result concurrently, as they arrive; the
+    /// partial results are then merged into the final result. See `Aggregator` for details.
+    pub async fn scan_fold<T, A>(
+        &self,
+        policy: &ScanPolicy,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+        aggregator: &Aggregator<A>,
+    ) -> Result<A>
+    where
+        T: Into<Bins> + Send + Sync + 'static,
+        A: 'static,
+    {
+        check_no_txn(&policy.base_policy, "scan")?;
+        let bins = bins.into();
+        let node_partitions = self.cluster.partitions_by_node(namespace, policy.replica);
+        let partials = node_partitions.into_iter().map(|(node, partitions)| {
+            let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
+            let t_recordset = recordset.clone();
+            let policy = policy.clone();
+            let namespace = namespace.to_owned();
+            let set_name = set_name.to_owned();
+            let bins = bins.clone();
+            aerospike_rt::spawn(async move {
+                let mut command = ScanCommand::new(
+                    &policy,
+                    node,
+                    &namespace,
+                    &set_name,
+                    bins,
+                    t_recordset.clone(),
+                    partitions,
+                );
+                if let Err(err) = command.execute().await {
+                    t_recordset.push_error(err).await;
+                }
+            });
+            async move { aggregator.fold_recordset(&recordset).await }
+        });
+
+        let partials = futures::future::join_all(partials)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<A>>>()?;
+        Ok(aggregator.combine(partials))
+    }
+
     /// Read all records in the specified namespace and set for one node only and return a record
     /// iterator. The scan executor puts records on a queue in separate threads. The calling thread
     /// concurrently pops records off the queue through the record iterator. Up to
@@ -688,6 +1121,7 @@ impl Client {
     where
         T: Into<Bins> + Send + Sync + 'static,
     {
+        check_no_txn(&policy.base_policy, "scan")?;
         let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
         let bins = bins.into();
         let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
@@ -743,16 +1177,18 @@ impl Client {
         policy: &QueryPolicy,
         statement: Statement,
     ) -> Result<Arc<Recordset>> {
+        check_no_txn(&policy.base_policy, "query")?;
         statement.validate()?;
         let statement = Arc::new(statement);
 
-        let nodes = self.cluster.nodes();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, nodes.len()));
-        for node in nodes {
-            let partitions = self
-                .cluster
-                .node_partitions(node.as_ref(), &statement.namespace);
-            let node = node.clone();
+        let node_partitions = self
+            .cluster
+            .partitions_by_node(&statement.namespace, policy.replica);
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            node_partitions.len(),
+        ));
+        for (node, partitions) in node_partitions {
             let t_recordset = recordset.clone();
             let policy = policy.clone();
             let statement = statement.clone();
@@ -766,6 +1202,87 @@ impl Client {
         Ok(recordset)
     }
 
+    /// Execute a stream UDF aggregation query and return the aggregated values. The aggregate
+    /// function must be set on the statement using `Statement::set_aggregate_function`.
+    ///
+    /// Each server node runs the stream on its records, up to and including the first `reduce`
+    /// operation, and returns its partial results. The client then runs the final stages of the
+    /// stream, starting with that `reduce` operation, on the partial results of all nodes. For
+    /// this, `udf_body` must contain the source of the UDF package registered on the server.
+    ///
+    /// Requires the `lua` feature.
+    #[cfg(feature = "lua")]
+    pub async fn query_aggregate(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        udf_body: &[u8],
+    ) -> Result<Vec<Value>> {
+        let aggregation = match statement.aggregation {
+            Some(ref aggregation) => aggregation.clone(),
+            None => bail!(ErrorKind::InvalidArgument(
+                "Statement has no aggregate function".to_string()
+            )),
+        };
+
+        let recordset = self.query(policy, statement).await?;
+        let mut values = vec![];
+        while let Some(record) = recordset.next_record().await {
+            // Partial results are returned in bin "SUCCESS"; bin "FAILURE" contains details
+            // about UDF errors.
+            let mut record = record?;
+            match record.bins.remove("SUCCESS") {
+                Some(value) => values.push(value),
+                None => bail!(ErrorKind::UdfBadResponse(
+                    record
+                        .bins
+                        .get("FAILURE")
+                        .map_or(String::from("UDF Error"), ToString::to_string)
+                )),
+            }
+        }
+
+        crate::query::aggregate::apply_stream(udf_body, &aggregation, &values)
+    }
+
+    /// Execute a query on all server nodes and aggregate the records on the client. The records
+    /// of each node are folded into a partial result concurrently, as they arrive; the partial
+    /// results are then merged into the final result. See `Aggregator` for details.
+    pub async fn query_fold<A: 'static>(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        aggregator: &Aggregator<A>,
+    ) -> Result<A> {
+        check_no_txn(&policy.base_policy, "query")?;
+        statement.validate()?;
+        let statement = Arc::new(statement);
+
+        let node_partitions = self
+            .cluster
+            .partitions_by_node(&statement.namespace, policy.replica);
+        let partials = node_partitions.into_iter().map(|(node, partitions)| {
+            let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
+            let t_recordset = recordset.clone();
+            let policy = policy.clone();
+            let statement = statement.clone();
+            aerospike_rt::spawn(async move {
+                let mut command =
+                    QueryCommand::new(&policy, node, statement, t_recordset.clone(), partitions);
+                if let Err(err) = command.execute().await {
+                    t_recordset.push_error(err).await;
+                }
+            });
+            async move { aggregator.fold_recordset(&recordset).await }
+        });
+
+        let partials = futures::future::join_all(partials)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<A>>>()?;
+        Ok(aggregator.combine(partials))
+    }
+
     /// Execute a query on a single server node and return a record iterator. The query executor
     /// puts records on a queue in separate threads. The calling thread concurrently pops records
     /// off the queue through the record iterator.
@@ -778,6 +1295,7 @@ impl Client {
         node: Arc<Node>,
         statement: Statement,
     ) -> Result<Arc<Recordset>> {
+        check_no_txn(&policy.base_policy, "query")?;
         statement.validate()?;
 
         let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
@@ -885,15 +1403,49 @@ impl Client {
         index_type: IndexType,
         collection_index_type: CollectionIndexType,
     ) -> Result<()> {
+        self.create_complex_index_with_context(
+            namespace,
+            set_name,
+            bin_name,
+            index_name,
+            index_type,
+            collection_index_type,
+            &[],
+        )
+        .await
+    }
+
+    /// Create a complex secondary index on a nested list or map element of a bin. The element is
+    /// identified by the context path `ctx`. This asynchronous server call returns before the
+    /// command is complete.
+    ///
+    /// Queries on the index need to set the same context on their filter, see
+    /// `Filter::set_context`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_complex_index_with_context(
+        &self,
+        namespace: &str,
+        set_name: &str,
+        bin_name: &str,
+        index_name: &str,
+        index_type: IndexType,
+        collection_index_type: CollectionIndexType,
+        ctx: &[CdtContext],
+    ) -> Result<()> {
+        let ctx_str: String = if ctx.is_empty() {
+            "".to_string()
+        } else {
+            format!("context={};", CdtContext::to_base64(ctx))
+        };
         let cit_str: String = if let CollectionIndexType::Default = collection_index_type {
             "".to_string()
         } else {
             format!("indextype={};", collection_index_type)
         };
         let cmd = format!(
-            "sindex-create:ns={};set={};indexname={};numbins=1;{}indexdata={},{};\
+            "sindex-create:ns={};set={};indexname={};numbins=1;{}{}indexdata={},{};\
              priority=normal",
-            namespace, set_name, index_name, cit_str, bin_name, index_type
+            namespace, set_name, index_name, ctx_str, cit_str, bin_name, index_type
         );
         self.send_info_cmd(&cmd)
             .await
@@ -921,6 +1473,189 @@ impl Client {
             .chain_err(|| "Error dropping index")
     }
 
+    /// List the secondary indexes of a namespace, including their load progress and number of
+    /// entries across all nodes of the cluster.
+    pub async fn list_indexes(&self, namespace: &str) -> Result<Vec<IndexInfo>> {
+        let node = self.cluster.get_random_node()?;
+        let cmd = format!("sindex-list:ns={}", namespace);
+        let response = node.info(&[&cmd]).await?;
+        let mut indexes = match response.get(&cmd) {
+            Some(list) => IndexInfo::parse_list(list).chain_err(|| "Error listing indexes")?,
+            None => vec![],
+        };
+        indexes.retain(|index| index.namespace == namespace);
+
+        // Request the statistics of all indexes from every node at once.
+        let commands: Vec<String> = indexes
+            .iter()
+            .map(|index| format!("sindex/{}/{}", namespace, index.name))
+            .collect();
+        let commands: Vec<&str> = commands.iter().map(String::as_str).collect();
+        let nodes = self.cluster.nodes();
+        let responses =
+            futures::future::join_all(nodes.iter().map(|node| node.info(&commands))).await;
+
+        for index in &mut indexes {
+            index.load_pct = 100;
+        }
+        for response in responses {
+            let response = response?;
+            for (index, cmd) in indexes.iter_mut().zip(&commands) {
+                if let Some(stats) = response.get(*cmd) {
+                    index
+                        .add_node_stats(stats)
+                        .chain_err(|| "Error reading index statistics")?;
+                }
+            }
+        }
+
+        Ok(indexes)
+    }
+
+    /// Returns the names of the namespaces configured on any of the cluster nodes.
+    pub async fn namespaces(&self) -> Result<Vec<String>> {
+        let mut namespaces: Vec<String> = vec![];
+        for (_, response) in self.info_on_all_nodes("namespaces").await? {
+            for ns in response.split(';').filter(|ns| !ns.is_empty()) {
+                if !namespaces.iter().any(|known| known == ns) {
+                    namespaces.push(ns.to_string());
+                }
+            }
+        }
+        Ok(namespaces)
+    }
+
+    /// Returns the configuration and statistics of a namespace, keyed by node name.
+    pub async fn namespace_info(&self, namespace: &str) -> Result<HashMap<String, NamespaceInfo>> {
+        let cmd = format!("namespace/{}", namespace);
+        let mut infos = HashMap::new();
+        for (node, response) in self.info_on_all_nodes(&cmd).await? {
+            let info = NamespaceInfo::parse(namespace, &response)
+                .chain_err(|| format!("Error reading namespace info from node {}", node))?;
+            infos.insert(node, info);
+        }
+        Ok(infos)
+    }
+
+    /// Returns the statistics of all sets in a namespace, aggregated across all cluster nodes.
+    pub async fn sets(&self, namespace: &str) -> Result<Vec<SetInfo>> {
+        let cmd = format!("sets/{}", namespace);
+        let mut sets: Vec<SetInfo> = vec![];
+        for (node, response) in self.info_on_all_nodes(&cmd).await? {
+            let node_sets = SetInfo::parse_list(&response)
+                .chain_err(|| format!("Error reading set info from node {}", node))?;
+            for set in node_sets {
+                match sets.iter_mut().find(|known| known.name == set.name) {
+                    Some(known) => known.merge(&set),
+                    None => sets.push(set),
+                }
+            }
+        }
+        Ok(sets)
+    }
+
+    /// Returns the bin names used in a namespace on any of the cluster nodes.
+    pub async fn bins(&self, namespace: &str) -> Result<Vec<String>> {
+        let cmd = format!("bins/{}", namespace);
+        let mut bins: Vec<String> = vec![];
+        for (_, response) in self.info_on_all_nodes(&cmd).await? {
+            for bin in info::parse_bins(&response) {
+                if !bins.contains(&bin) {
+                    bins.push(bin);
+                }
+            }
+        }
+        Ok(bins)
+    }
+
+    /// Returns the statistics of each cluster node, keyed by node name.
+    pub async fn node_statistics(&self) -> Result<HashMap<String, HashMap<String, String>>> {
+        let mut stats = HashMap::new();
+        for (node, response) in self.info_on_all_nodes("statistics").await? {
+            let node_stats = info::parse_stats(&response, ';')
+                .chain_err(|| format!("Error reading statistics from node {}", node))?
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            stats.insert(node, node_stats);
+        }
+        Ok(stats)
+    }
+
+    /// Returns the server build version of each cluster node, keyed by node name.
+    pub async fn build_versions(&self) -> Result<HashMap<String, String>> {
+        Ok(self.info_on_all_nodes("build").await?.into_iter().collect())
+    }
+
+    /// Sends the info commands to every active cluster node concurrently and returns the
+    /// responses, keyed by node name. The response of each node maps the commands to their
+    /// values, like `Node::info`.
+    ///
+    /// A failure to reach a node is reported in the result for that node. If
+    /// `require_agreement` is set, the call instead fails unless all nodes respond and all
+    /// responses are identical.
+    ///
+    /// # Examples
+    ///
+    /// Check that all nodes run the same server version.
+    ///
+    /// ```rust,edition2018
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).await.unwrap();
+    /// match client.info_all(&["build"], true).await {
+    ///     Ok(responses) => println!("All {} nodes agree", responses.len()),
+    ///     Err(err) => println!("Nodes disagree: {}", err),
+    /// }
+    /// ```
+    pub async fn info_all(
+        &self,
+        commands: &[&str],
+        require_agreement: bool,
+    ) -> Result<HashMap<String, Result<HashMap<String, String>>>> {
+        let nodes = self.cluster.nodes();
+        let requests = nodes.iter().map(|node| node.info(commands));
+        let responses: HashMap<String, Result<HashMap<String, String>>> = nodes
+            .iter()
+            .map(|node| node.name().to_string())
+            .zip(futures::future::join_all(requests).await)
+            .collect();
+
+        if require_agreement {
+            let mut expected: Option<&HashMap<String, String>> = None;
+            for (node, response) in &responses {
+                let response = match response {
+                    Ok(response) => response,
+                    Err(err) => bail!(ErrorKind::BadResponse(format!(
+                        "Info command failed on node {}: {}",
+                        node, err
+                    ))),
+                };
+                match expected {
+                    Some(expected) if expected != response => bail!(ErrorKind::BadResponse(
+                        format!("Info responses differ between nodes: {:?}", commands)
+                    )),
+                    _ => expected = Some(response),
+                }
+            }
+        }
+
+        Ok(responses)
+    }
+
+    // Sends the info command to every active node and returns the responses, keyed by node
+    // name. Fails if any of the nodes cannot be reached.
+    async fn info_on_all_nodes(&self, cmd: &str) -> Result<Vec<(String, String)>> {
+        let mut responses = vec![];
+        for (node, response) in self.info_all(&[cmd], false).await? {
+            if let Some(value) = response?.remove(cmd) {
+                responses.push((node, value));
+            }
+        }
+        Ok(responses)
+    }
+
     async fn send_info_cmd(&self, cmd: &str) -> Result<()> {
         let node = self.cluster.get_random_node()?;
         let response = node.info(&[cmd]).await?;
@@ -939,3 +1674,14 @@ impl Client {
         ))
     }
 }
+
+// Batch, scan and query commands do not take part in transactions.
+fn check_no_txn(policy: &BasePolicy, command: &str) -> Result<()> {
+    if policy.txn.is_some() {
+        bail!(ErrorKind::InvalidArgument(format!(
+            "Transactions are not supported by {} commands",
+            command
+        )));
+    }
+    Ok(())
+}
@@ -20,7 +20,7 @@ pub mod partition_tokenizer;
 
 use aerospike_rt::time::{Duration, Instant};
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
 use std::sync::{Arc, Weak};
 use std::vec::Vec;
 
@@ -56,7 +56,13 @@ impl PartitionForNamespace {
         (0..self.replicas).map(move |i|self.nodes.get(i * node::PARTITIONS + index).and_then(|(_, item)|item.clone()))
     }
 
-    fn get_node(&self, cluster: &Cluster, partition: &Partition<'_>, replica: crate::policy::Replica, last_tried: Weak<Node>) -> Result<Arc<Node>> {
+    fn get_node(
+        &self,
+        cluster: &Cluster,
+        partition: &Partition<'_>,
+        replica: crate::policy::Replica,
+        last_tried: Weak<Node>,
+    ) -> Result<Arc<Node>> {
         fn get_next_in_sequence<I: Iterator<Item = Arc<Node>>, F: Fn()->I>(get_sequence: F, last_tried: Weak<Node>) -> Option<Arc<Node>> {
             if let Some(last_tried) = last_tried.upgrade() {
                 // If this isn't the first attempt, try the replica immediately after in sequence (that is actually valid)
@@ -77,23 +83,68 @@ impl PartitionForNamespace {
         }
 
 
+        // Nodes that exceeded the max. error rate are skipped until their error count is reset.
+        let healthy_replicas = || {
+            self.all_replicas(partition.partition_id)
+                .flatten()
+                .filter(|node| node.error_rate_within_limit())
+        };
+
         let node = match replica {
-            crate::policy::Replica::Master => self.all_replicas(partition.partition_id).next().flatten(),
+            crate::policy::Replica::Master => self.all_replicas(partition.partition_id).next().flatten().filter(|node|node.error_rate_within_limit()),
             crate::policy::Replica::Sequence => {
-                get_next_in_sequence(||self.all_replicas(partition.partition_id).flatten(), last_tried)
+                get_next_in_sequence(healthy_replicas, last_tried)
+            },
+            crate::policy::Replica::MasterProles | crate::policy::Replica::Random => {
+                if last_tried.upgrade().is_some() {
+                    // Retries move on to the next replica, just like Replica::Sequence.
+                    get_next_in_sequence(healthy_replicas, last_tried)
+                } else {
+                    let candidates: Vec<Arc<Node>> = healthy_replicas().collect();
+                    let index = match replica {
+                        crate::policy::Replica::Random => rand::random::<usize>(),
+                        _ => cluster.replica_index.fetch_add(1, Ordering::Relaxed),
+                    };
+                    candidates.get(index.checked_rem(candidates.len()).unwrap_or_default()).cloned()
+                }
             },
             crate::policy::Replica::PreferRack => {
                 let rack_ids = cluster.client_policy.rack_ids.as_ref().ok_or_else(||"Attempted to use Replica::PreferRack without configuring racks in client policy".to_string())?;
-                get_next_in_sequence(||
-                    self
-                    .all_replicas(partition.partition_id)
-                    .flatten()
-                    .filter(|node|node.is_in_rack(partition.namespace, rack_ids)), last_tried.clone())
-                .or_else(||get_next_in_sequence(||self.all_replicas(partition.partition_id).flatten(), last_tried))
+                // Replicas on the client racks come first, in the configured order, followed by
+                // all remaining replicas in sequence.
+                let mut sequence: Vec<Arc<Node>> = Vec::with_capacity(self.replicas);
+                for rack_id in rack_ids {
+                    sequence.extend(healthy_replicas().filter(|node|node.rack_id(partition.namespace) == Some(*rack_id)));
+                }
+                for node in healthy_replicas() {
+                    if !sequence.iter().any(|n|Arc::ptr_eq(n, &node)) {
+                        sequence.push(node);
+                    }
+                }
+                get_next_in_sequence(||sequence.iter().cloned(), last_tried)
             },
         };
-        
-        node.ok_or_else(||format!("Cannot get appropriate node for namespace: {} partition: {}", partition.namespace, partition.partition_id).into())
+
+        node.ok_or_else(|| {
+            let candidate = match replica {
+                crate::policy::Replica::Master => {
+                    self.all_replicas(partition.partition_id).next().flatten()
+                }
+                _ => self.all_replicas(partition.partition_id).flatten().next(),
+            };
+            match candidate {
+                Some(node) => ErrorKind::MaxErrorRate(format!(
+                    "node {} for namespace: {} partition: {}",
+                    node, partition.namespace, partition.partition_id
+                ))
+                .into(),
+                None => format!(
+                    "Cannot get appropriate node for namespace: {} partition: {}",
+                    partition.namespace, partition.partition_id
+                )
+                .into(),
+            }
+        })
     }
 }
 
@@ -116,6 +167,12 @@ pub struct Cluster {
     // Random node index.
     node_index: AtomicIsize,
 
+    // Number of completed tend iterations.
+    tend_count: AtomicUsize,
+
+    // Round-robin replica index for Replica::MasterProles.
+    replica_index: AtomicUsize,
+
     client_policy: ClientPolicy,
 
     tend_channel: Mutex<Sender<()>>,
@@ -134,6 +191,8 @@ impl Cluster {
 
             partition_write_map: Mutex::new(HashMap::default()),
             node_index: AtomicIsize::new(0),
+            tend_count: AtomicUsize::new(0),
+            replica_index: AtomicUsize::new(0),
 
             tend_channel: Mutex::new(tx),
             closed: AtomicBool::new(false),
@@ -190,6 +249,8 @@ impl Cluster {
             nodes = self.nodes();
         }
 
+        self.update_error_rate_window(&nodes);
+
         let mut friend_list: Vec<Host> = vec![];
         let mut refresh_count = 0;
 
@@ -213,6 +274,8 @@ impl Cluster {
                         if old_rebalance_gen != node.rebalance_generation() {
                             self.update_rack_ids(&node).await?;
                         }
+
+                        node.balance_connections();
                     }
                     Err(err) => {
                         node.increase_failures();
@@ -235,6 +298,21 @@ impl Cluster {
         Ok(())
     }
 
+    // Counts the tend iteration and starts a new error rate window for all nodes once
+    // `error_rate_window` iterations have passed.
+    fn update_error_rate_window(&self, nodes: &[Arc<Node>]) {
+        let tend_count = self.tend_count.fetch_add(1, Ordering::Relaxed) + 1;
+        let error_rate_window = self.client_policy.error_rate_window;
+        if self.client_policy.max_error_rate > 0
+            && error_rate_window > 0
+            && tend_count % error_rate_window == 0
+        {
+            for node in nodes {
+                node.reset_error_count();
+            }
+        }
+    }
+
     async fn wait_till_stabilized(cluster: Arc<Cluster>) -> Result<()> {
         let timeout = cluster
             .client_policy()
@@ -300,6 +378,45 @@ impl Cluster {
         res
     }
 
+    // Assigns every partition of the namespace to the node chosen by the replica policy, so
+    // that scans and queries spread across the cluster the same way single record commands do.
+    // Partitions without a healthy candidate stay on their master node. For an unknown namespace,
+    // every node gets an empty partition list, so that scans and queries return no records.
+    pub fn partitions_by_node(
+        &self,
+        namespace: &str,
+        replica: crate::policy::Replica,
+    ) -> Vec<(Arc<Node>, Vec<u16>)> {
+        let mut res: Vec<(Arc<Node>, Vec<u16>)> = vec![];
+        let partitions = self.partition_write_map.lock().unwrap();
+
+        let Some(node_array) = partitions.get(namespace) else {
+            return self
+                .nodes()
+                .into_iter()
+                .map(|node| (node, vec![]))
+                .collect();
+        };
+
+        for partition_id in 0..node::PARTITIONS {
+            let partition = Partition::new(namespace, partition_id);
+            let node = match node_array.get_node(self, &partition, replica, Weak::new()) {
+                Ok(node) => node,
+                Err(_) => match node_array.all_replicas(partition_id).next().flatten() {
+                    Some(node) => node,
+                    None => continue,
+                },
+            };
+
+            match res.iter_mut().find(|(n, _)| Arc::ptr_eq(n, &node)) {
+                Some((_, node_partitions)) => node_partitions.push(partition_id as u16),
+                None => res.push((node, vec![partition_id as u16])),
+            }
+        }
+
+        res
+    }
+
     pub async fn update_partitions(&self, node: &Arc<Node>) -> Result<()> {
         let mut conn = node.get_connection().await?;
         let tokens = PartitionTokenizer::new(&mut conn, node).await.map_err(|e| {
@@ -462,6 +579,7 @@ impl Cluster {
 
     fn add_nodes_and_aliases(&self, friend_list: &[Arc<Node>]) {
         for node in friend_list {
+            node.create_min_connections();
             self.add_aliases(node.clone());
         }
         self.add_nodes(friend_list);
@@ -600,3 +718,316 @@ impl Cluster {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize};
+    use std::sync::{Arc, Mutex, Weak};
+
+    use futures::channel::mpsc;
+
+    use super::node_validator::{NodeFeatures, NodeValidator};
+    use super::partition::Partition;
+    use super::{node, Cluster, Node, PartitionForNamespace};
+    use crate::errors::{Error, ErrorKind};
+    use crate::net::Host;
+    use crate::policy::{ClientPolicy, Replica};
+
+    fn test_cluster(policy: ClientPolicy) -> Cluster {
+        let (tx, _) = mpsc::channel(1);
+        Cluster {
+            seeds: Arc::new(Mutex::new(vec![])),
+            aliases: Arc::new(Mutex::new(HashMap::new())),
+            nodes: Arc::new(Mutex::new(vec![])),
+            partition_write_map: Mutex::new(HashMap::new()),
+            node_index: AtomicIsize::new(0),
+            tend_count: AtomicUsize::new(0),
+            replica_index: AtomicUsize::new(0),
+            client_policy: policy,
+            tend_channel: Mutex::new(tx),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn test_node(policy: &ClientPolicy, name: &str) -> Arc<Node> {
+        let nv = NodeValidator {
+            name: name.to_string(),
+            aliases: vec![Host::new("127.0.0.1", 3000)],
+            address: "127.0.0.1:3000".to_string(),
+            client_policy: policy.clone(),
+            use_new_info: true,
+            features: NodeFeatures::default(),
+        };
+        Arc::new(Node::new(policy.clone(), Arc::new(nv)))
+    }
+
+    // Every partition has the same replica list, master first.
+    fn partition_map(replicas: &[Arc<Node>]) -> PartitionForNamespace {
+        let mut nodes = Vec::with_capacity(replicas.len() * node::PARTITIONS);
+        for replica in replicas {
+            nodes.extend((0..node::PARTITIONS).map(|_| (0, Some(replica.clone()))));
+        }
+        PartitionForNamespace {
+            nodes,
+            replicas: replicas.len(),
+        }
+    }
+
+    fn add_errors(node: &Node, count: usize) {
+        for _ in 0..count {
+            node.add_error();
+        }
+    }
+
+    #[test]
+    fn error_rate_within_limit() {
+        let policy = ClientPolicy {
+            max_error_rate: 2,
+            ..ClientPolicy::default()
+        };
+        let node = test_node(&policy, "A");
+        assert!(node.error_rate_within_limit());
+        add_errors(&node, 2);
+        assert!(node.error_rate_within_limit());
+        node.add_error();
+        assert!(!node.error_rate_within_limit());
+        node.reset_error_count();
+        assert!(node.error_rate_within_limit());
+
+        // a limit of zero disables the circuit breaker
+        let policy = ClientPolicy {
+            max_error_rate: 0,
+            ..ClientPolicy::default()
+        };
+        let node = test_node(&policy, "A");
+        add_errors(&node, 10);
+        assert_eq!(node.error_count(), 0);
+        assert!(node.error_rate_within_limit());
+    }
+
+    #[test]
+    fn error_rate_window_reset() {
+        let policy = ClientPolicy {
+            max_error_rate: 1,
+            error_rate_window: 3,
+            ..ClientPolicy::default()
+        };
+        let cluster = test_cluster(policy.clone());
+        let nodes = vec![test_node(&policy, "A"), test_node(&policy, "B")];
+        add_errors(&nodes[0], 5);
+        add_errors(&nodes[1], 1);
+
+        cluster.update_error_rate_window(&nodes);
+        cluster.update_error_rate_window(&nodes);
+        assert_eq!(nodes[0].error_count(), 5);
+        assert_eq!(nodes[1].error_count(), 1);
+
+        cluster.update_error_rate_window(&nodes);
+        assert_eq!(nodes[0].error_count(), 0);
+        assert_eq!(nodes[1].error_count(), 0);
+    }
+
+    #[test]
+    fn skip_node_over_error_rate() {
+        let policy = ClientPolicy {
+            max_error_rate: 1,
+            ..ClientPolicy::default()
+        };
+        let cluster = test_cluster(policy.clone());
+        let (a, b) = (test_node(&policy, "A"), test_node(&policy, "B"));
+        let map = partition_map(&[a.clone(), b.clone()]);
+        let partition = Partition::new("test", 0);
+        add_errors(&a, 2);
+
+        for replica in [Replica::Sequence, Replica::MasterProles, Replica::Random] {
+            let res = map
+                .get_node(&cluster, &partition, replica, Weak::new())
+                .unwrap();
+            assert!(Arc::ptr_eq(&res, &b));
+        }
+
+        // Replica::Master has no other candidate
+        let res = map.get_node(&cluster, &partition, Replica::Master, Weak::new());
+        assert!(matches!(res, Err(Error(ErrorKind::MaxErrorRate(_), _))));
+    }
+
+    #[test]
+    fn all_nodes_over_error_rate() {
+        let policy = ClientPolicy {
+            max_error_rate: 1,
+            ..ClientPolicy::default()
+        };
+        let cluster = test_cluster(policy.clone());
+        let (a, b) = (test_node(&policy, "A"), test_node(&policy, "B"));
+        let map = partition_map(&[a.clone(), b.clone()]);
+        let partition = Partition::new("test", 0);
+        add_errors(&a, 2);
+        add_errors(&b, 2);
+
+        for replica in [
+            Replica::Master,
+            Replica::Sequence,
+            Replica::MasterProles,
+            Replica::Random,
+        ] {
+            let res = map.get_node(&cluster, &partition, replica, Weak::new());
+            assert!(matches!(res, Err(Error(ErrorKind::MaxErrorRate(_), _))));
+        }
+
+        // without any node the error is not about the error rate
+        let map = PartitionForNamespace::default();
+        let res = map.get_node(&cluster, &partition, Replica::Sequence, Weak::new());
+        assert!(!matches!(res, Err(Error(ErrorKind::MaxErrorRate(_), _))));
+    }
+
+    #[test]
+    fn master_proles_and_random() {
+        let policy = ClientPolicy::default();
+        let cluster = test_cluster(policy.clone());
+        let nodes = [
+            test_node(&policy, "A"),
+            test_node(&policy, "B"),
+            test_node(&policy, "C"),
+        ];
+        let map = partition_map(&nodes);
+        let partition = Partition::new("test", 0);
+
+        // round-robin over master and proles
+        for i in 0..6 {
+            let res = map
+                .get_node(&cluster, &partition, Replica::MasterProles, Weak::new())
+                .unwrap();
+            assert!(Arc::ptr_eq(&res, &nodes[i % 3]));
+        }
+
+        for _ in 0..20 {
+            let res = map
+                .get_node(&cluster, &partition, Replica::Random, Weak::new())
+                .unwrap();
+            assert!(nodes.iter().any(|node| Arc::ptr_eq(node, &res)));
+        }
+
+        // retries move on to the next replica in sequence, wrapping around after the last one
+        for replica in [Replica::MasterProles, Replica::Random] {
+            let res = map
+                .get_node(&cluster, &partition, replica, Arc::downgrade(&nodes[0]))
+                .unwrap();
+            assert!(Arc::ptr_eq(&res, &nodes[1]));
+            let res = map
+                .get_node(&cluster, &partition, replica, Arc::downgrade(&nodes[2]))
+                .unwrap();
+            assert!(Arc::ptr_eq(&res, &nodes[0]));
+        }
+    }
+
+    #[test]
+    fn prefer_rack_order() {
+        let policy = ClientPolicy {
+            rack_ids: Some(vec![2, 1]),
+            ..ClientPolicy::default()
+        };
+        let cluster = test_cluster(policy.clone());
+        let nodes = [
+            test_node(&policy, "A"),
+            test_node(&policy, "B"),
+            test_node(&policy, "C"),
+        ];
+        nodes[0].parse_rack("test:3").unwrap();
+        nodes[1].parse_rack("test:1").unwrap();
+        nodes[2].parse_rack("test:2").unwrap();
+        let map = partition_map(&nodes);
+        let partition = Partition::new("test", 0);
+
+        let mut order = vec![];
+        let mut last_tried = Weak::new();
+        for _ in 0..3 {
+            let node = map
+                .get_node(&cluster, &partition, Replica::PreferRack, last_tried)
+                .unwrap();
+            last_tried = Arc::downgrade(&node);
+            order.push(node.name().to_string());
+        }
+        assert_eq!(order, ["C", "B", "A"]);
+
+        // the rack order decides which replica is tried first
+        let policy = ClientPolicy {
+            rack_ids: Some(vec![1, 2]),
+            ..policy
+        };
+        let cluster = test_cluster(policy);
+        let res = map
+            .get_node(&cluster, &partition, Replica::PreferRack, Weak::new())
+            .unwrap();
+        assert!(Arc::ptr_eq(&res, &nodes[1]));
+        let res = map
+            .get_node(
+                &cluster,
+                &partition,
+                Replica::PreferRack,
+                Arc::downgrade(&res),
+            )
+            .unwrap();
+        assert!(Arc::ptr_eq(&res, &nodes[2]));
+
+        // Replica::PreferRack requires racks in the client policy
+        let cluster = test_cluster(ClientPolicy::default());
+        assert!(map
+            .get_node(&cluster, &partition, Replica::PreferRack, Weak::new())
+            .is_err());
+    }
+
+    #[test]
+    fn partitions_by_node() {
+        let policy = ClientPolicy {
+            max_error_rate: 1,
+            ..ClientPolicy::default()
+        };
+        let cluster = test_cluster(policy.clone());
+        let (a, b) = (test_node(&policy, "A"), test_node(&policy, "B"));
+        cluster.set_nodes(vec![a.clone(), b.clone()]);
+
+        // A is master of the even partitions, B of the odd ones
+        let mut nodes = Vec::with_capacity(2 * node::PARTITIONS);
+        for replica in 0..2 {
+            nodes.extend((0..node::PARTITIONS).map(|partition_id| {
+                let node = if (partition_id + replica) % 2 == 0 {
+                    &a
+                } else {
+                    &b
+                };
+                (0, Some(node.clone()))
+            }));
+        }
+        let map = PartitionForNamespace { nodes, replicas: 2 };
+        cluster
+            .partition_write_map
+            .lock()
+            .unwrap()
+            .insert("test".to_string(), map);
+
+        let res = cluster.partitions_by_node("test", Replica::Master);
+        assert_eq!(res.len(), 2);
+        for (node, partitions) in &res {
+            let even = Arc::ptr_eq(node, &a);
+            assert_eq!(partitions.len(), node::PARTITIONS / 2);
+            assert!(partitions.iter().all(|id| (id % 2 == 0) == even));
+        }
+
+        // A exceeded its error rate; the replicas on B take over its partitions
+        add_errors(&a, 2);
+        let res = cluster.partitions_by_node("test", Replica::Sequence);
+        assert_eq!(res.len(), 1);
+        assert!(Arc::ptr_eq(&res[0].0, &b));
+        assert_eq!(res[0].1.len(), node::PARTITIONS);
+
+        // with no healthy candidate, partitions stay on their master
+        let res = cluster.partitions_by_node("test", Replica::Master);
+        assert_eq!(res.len(), 2);
+
+        // unknown namespaces give every node an empty partition list
+        let res = cluster.partitions_by_node("other", Replica::Master);
+        assert_eq!(res.len(), 2);
+        assert!(res.iter().all(|(_, partitions)| partitions.is_empty()));
+    }
+}
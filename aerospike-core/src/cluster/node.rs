@@ -13,7 +13,7 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::result::Result as StdResult;
@@ -26,6 +26,7 @@ use crate::commands::Message;
 use crate::errors::{ErrorKind, Result, ResultExt};
 use crate::net::{ConnectionPool, Host, PooledConnection};
 use crate::policy::ClientPolicy;
+use aerospike_rt::time::Instant;
 
 pub const PARTITIONS: usize = 4096;
 pub const PARTITION_GENERATION: &str = "partition-generation";
@@ -43,6 +44,7 @@ pub struct Node {
 
     connection_pool: ConnectionPool,
     failures: AtomicUsize,
+    error_count: AtomicUsize,
 
     partition_generation: AtomicIsize,
     rebalance_generation: AtomicIsize,
@@ -52,6 +54,8 @@ pub struct Node {
     reference_count: AtomicUsize,
     responded: AtomicBool,
     active: AtomicBool,
+    // Set while a background task is filling or balancing the connection pool.
+    pool_task_running: AtomicBool,
 
     features: NodeFeatures,
 }
@@ -69,11 +73,13 @@ impl Node {
             rebalance_generation: AtomicIsize::new(if client_policy.rack_ids.is_some() {-1} else {0}),
             connection_pool: ConnectionPool::new(nv.aliases[0].clone(), client_policy),
             failures: AtomicUsize::new(0),
+            error_count: AtomicUsize::new(0),
             partition_generation: AtomicIsize::new(-1),
             refresh_count: AtomicUsize::new(0),
             reference_count: AtomicUsize::new(0),
             responded: AtomicBool::new(false),
             active: AtomicBool::new(true),
+            pool_task_running: AtomicBool::new(false),
             features: nv.features,
             rack_ids: std::sync::Mutex::new(HashMap::new()),
         }
@@ -258,12 +264,14 @@ impl Node {
         Ok(())
     }
 
-    pub fn is_in_rack(&self, namespace: &str, rack_ids: &HashSet<usize>) -> bool {
-        if let Ok(locked) = self.rack_ids.lock() {
-            locked.get(namespace).map_or(false, |r|rack_ids.contains(r))
-        } else {
-            false
-        }
+    pub fn is_in_rack(&self, namespace: &str, rack_ids: &[usize]) -> bool {
+        self.rack_id(namespace)
+            .map_or(false, |r| rack_ids.contains(&r))
+    }
+
+    // Returns the rack of the node for the namespace, if known
+    pub fn rack_id(&self, namespace: &str) -> Option<usize> {
+        self.rack_ids.lock().ok()?.get(namespace).copied()
     }
 
     pub fn parse_rack(&self, buf: &str) -> Result<()> {
@@ -278,7 +286,57 @@ impl Node {
 
     // Get a connection to the node from the connection pool
     pub async fn get_connection(&self) -> Result<PooledConnection> {
-        self.connection_pool.get().await
+        self.connection_pool.get(None).await
+    }
+
+    // Get a connection to the node from the connection pool, waiting for a free connection
+    // until the given deadline at the latest if the pool is exhausted
+    pub async fn get_connection_with_deadline(
+        &self,
+        deadline: Option<Instant>,
+    ) -> Result<PooledConnection> {
+        self.connection_pool.get(deadline).await
+    }
+
+    // Open connections until the pool holds `min_conns_per_node` connections. Runs in the
+    // background, so that slow connection attempts do not hold up the cluster tend.
+    pub fn create_min_connections(self: &Arc<Self>) {
+        if self.client_policy.min_conns_per_node == 0 || !self.start_pool_task() {
+            return;
+        }
+        let node = self.clone();
+        let _res = aerospike_rt::spawn(async move {
+            match node.connection_pool.fill().await {
+                Ok(opened) => debug!("Node {}: opened {} initial connections", node, opened),
+                Err(err) => warn!(
+                    "Node {}: failed to create minimum connections: {}",
+                    node, err
+                ),
+            }
+            node.pool_task_running.store(false, Ordering::Release);
+        });
+    }
+
+    // Close excess idle connections and top the pool up to `min_conns_per_node`. Runs in the
+    // background like `create_min_connections`.
+    pub fn balance_connections(self: &Arc<Self>) {
+        if !self.start_pool_task() {
+            return;
+        }
+        let node = self.clone();
+        let _res = aerospike_rt::spawn(async move {
+            if let Err(err) = node.connection_pool.balance().await {
+                warn!("Node {}: failed to balance connections: {}", node, err);
+            }
+            node.pool_task_running.store(false, Ordering::Release);
+        });
+    }
+
+    // Returns false if a pool task is already running for the node; at most one runs at a time.
+    fn start_pool_task(&self) -> bool {
+        self.pool_task_running
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
     }
 
     // Amount of failures
@@ -295,6 +353,29 @@ impl Node {
         self.failures.fetch_add(1, Ordering::Relaxed)
     }
 
+    // Adds an error to the error count of the current error rate window
+    pub fn add_error(&self) {
+        if self.client_policy.max_error_rate > 0 {
+            self.error_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    // Number of errors in the current error rate window
+    pub fn error_count(&self) -> usize {
+        self.error_count.load(Ordering::Relaxed)
+    }
+
+    // Returns false if the node exceeded the max. error rate and should not be used for commands
+    pub fn error_rate_within_limit(&self) -> bool {
+        let max_error_rate = self.client_policy.max_error_rate;
+        max_error_rate == 0 || self.error_count() <= max_error_rate
+    }
+
+    // Resets the error count at the end of an error rate window
+    pub fn reset_error_count(&self) {
+        self.error_count.store(0, Ordering::Relaxed);
+    }
+
     fn inactivate(&self) {
         self.active.store(false, Ordering::Relaxed);
     }
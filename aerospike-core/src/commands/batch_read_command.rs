@@ -19,7 +19,7 @@ use std::sync::Arc;
 use crate::cluster::{Node, Cluster};
 use crate::cluster::partition::Partition;
 use crate::commands;
-use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
 use crate::net::Connection;
 use crate::policy::{BatchPolicy, Policy, PolicyLike, Replica};
 use crate::{value, BatchRead, Record, ResultCode, Value};
@@ -46,6 +46,10 @@ impl BatchReadCommand {
         }
     }
 
+    pub const fn policy(&self) -> &BatchPolicy {
+        &self.policy
+    }
+
     pub async fn execute(mut self, cluster: Arc<Cluster>) -> Result<Self> {
         let mut iterations = 0;
         let base_policy = self.policy.base().clone();
@@ -55,8 +59,9 @@ impl BatchReadCommand {
 
         // Execute command until successful, timed out or maximum iterations have been reached.
         loop {
-            let success = if iterations & 1 == 0 || matches!(self.policy.replica, Replica::Master) {
-                // For even iterations, we request all keys from the same node for efficiency.
+            let success = if (iterations & 1 == 0 && self.node.error_rate_within_limit()) || matches!(self.policy.replica, Replica::Master) {
+                // For even iterations, we request all keys from the same node for efficiency,
+                // unless that node exceeded its max. error rate.
                 Self::request_group(&mut self.batch_reads, &self.policy, self.node.clone(), deadline).await?
             } else {
                 // However, for odd iterations try the second choice for each. Instead of re-sharding the batch (as the second choice may not correspond to the first), just try each by itself.
@@ -105,10 +110,24 @@ impl BatchReadCommand {
         }
     }
 
-    async fn request_group(batch_reads: &mut [(BatchRead, usize)], policy: &BatchPolicy, node: Arc<Node>, deadline: Option<Instant>) -> Result<bool> {
-        let mut conn = match crate::commands::single_command::try_with_timeout(deadline, node.get_connection()).await {
+    async fn request_group(
+        batch_reads: &mut [(BatchRead, usize)],
+        policy: &BatchPolicy,
+        node: Arc<Node>,
+        deadline: Option<Instant>,
+    ) -> Result<bool> {
+        if !node.error_rate_within_limit() {
+            bail!(ErrorKind::MaxErrorRate(format!("node {}", node)));
+        }
+
+        let mut conn = match crate::commands::single_command::try_with_timeout(deadline, node.get_connection_with_deadline(deadline)).await {
             Ok(conn) => conn,
+            // Waiting for a free connection already used up the allowed time.
+            Err(err @ Error(ErrorKind::ConnectionWaitTimeout(_), _)) => return Err(err),
             Err(err) => {
+                if commands::is_node_error(&err) {
+                    node.add_error();
+                }
                 warn!("Node {}: {}", node, err);
                 return Ok(false);
             }
@@ -121,25 +140,33 @@ impl BatchReadCommand {
         conn.buffer.write_timeout(policy.base().timeout());
 
         // Send command.
+        conn.set_busy(true);
         if let Err(err) = commands::single_command::try_with_timeout(deadline, conn.flush()).await {
             // IO errors are considered temporary anomalies. Retry.
             // Close socket to flush out possible garbage. Do not put back in pool.
             conn.invalidate();
+            node.add_error();
             warn!("Node {}: {}", node, err);
             return Ok(false);
         }
 
         // Parse results.
         if let Err(err) = commands::single_command::try_with_timeout(deadline, Self::parse_result(batch_reads, &mut conn)).await {
+            if commands::is_node_error(&err) {
+                node.add_error();
+            }
             // close the connection
             // cancelling/closing the batch/multi commands will return an error, which will
             // close the connection to throw away its data and signal the server about the
             // situation. We will not put back the connection in the buffer.
-            if !commands::keep_connection(&err) {
+            if commands::keep_connection(&err) {
+                conn.set_busy(false);
+            } else {
                 conn.invalidate();
             }
             Err(err)
         } else {
+            conn.set_busy(false);
             Ok(true)
         }
     }
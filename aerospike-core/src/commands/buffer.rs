@@ -18,7 +18,7 @@ use std::time::Duration;
 use byteorder::{ByteOrder, LittleEndian, NetworkEndian};
 
 use crate::commands::field_type::FieldType;
-use crate::errors::Result;
+use crate::errors::{ErrorKind, Result};
 use crate::expressions::FilterExpression;
 use crate::msgpack::encoder;
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
@@ -26,7 +26,8 @@ use crate::policy::{
     BatchPolicy, CommitLevel, ConsistencyLevel, GenerationPolicy, QueryPolicy, BasePolicy,
     RecordExistsAction, ScanPolicy, WritePolicy,
 };
-use crate::{BatchRead, Bin, Bins, CollectionIndexType, Key, Statement, Value};
+use crate::txn::TxnFields;
+use crate::{BatchRead, Bin, Bins, CollectionIndexType, Key, Statement, Txn, Value};
 
 // Contains a read operation.
 const INFO1_READ: u8 = 1;
@@ -82,11 +83,24 @@ const INFO3_CREATE_OR_REPLACE: u8 = 1 << 4;
 // Completely replace existing record only.
 const INFO3_REPLACE_ONLY: u8 = 1 << 5;
 
+// Read the record with the consistency of a linearized read in a strong consistency namespace.
+const INFO3_SC_READ_TYPE: u8 = 1 << 6;
+
+// Verify that the version of a record read in a transaction is unchanged.
+const INFO4_MRT_VERIFY_READ: u8 = 1;
+
+// Roll forward the provisional write of a committed transaction.
+const INFO4_MRT_ROLL_FORWARD: u8 = 1 << 1;
+
+// Roll back the provisional write of an aborted transaction.
+const INFO4_MRT_ROLL_BACK: u8 = 1 << 2;
+
 pub const MSG_TOTAL_HEADER_SIZE: u8 = 30;
 const FIELD_HEADER_SIZE: u8 = 5;
 const OPERATION_HEADER_SIZE: u8 = 8;
 pub const MSG_REMAINING_HEADER_SIZE: u8 = 22;
 const DIGEST_SIZE: u8 = 20;
+const RECORD_VERSION_SIZE: u8 = 7;
 const CL_MSG_VERSION: u8 = 2;
 const AS_MSG_TYPE: u8 = 3;
 
@@ -166,6 +180,12 @@ impl Buffer {
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy
+            .base_policy
+            .txn
+            .as_ref()
+            .map(|txn| txn.fields(key, true));
+        field_count += self.estimate_txn_size(txn.as_ref());
 
         for bin in bins {
             self.estimate_operation_size_for_bin(bin.as_ref());
@@ -180,6 +200,7 @@ impl Buffer {
             bins.len() as u16,
         );
         self.write_key(key, policy.send_key);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -200,10 +221,17 @@ impl Buffer {
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy
+            .base_policy
+            .txn
+            .as_ref()
+            .map(|txn| txn.fields(key, true));
+        field_count += self.estimate_txn_size(txn.as_ref());
 
         self.size_buffer()?;
         self.write_header_with_policy(policy, 0, INFO2_WRITE | INFO2_DELETE, field_count as u16, 0);
         self.write_key(key, false);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -221,10 +249,17 @@ impl Buffer {
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy
+            .base_policy
+            .txn
+            .as_ref()
+            .map(|txn| txn.fields(key, true));
+        field_count += self.estimate_txn_size(txn.as_ref());
         self.estimate_operation_size();
         self.size_buffer()?;
         self.write_header_with_policy(policy, 0, INFO2_WRITE, field_count as u16, 1);
         self.write_key(key, policy.send_key);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -236,23 +271,20 @@ impl Buffer {
     }
 
     // Writes the command for exist operations
-    pub fn set_exists(&mut self, policy: &WritePolicy, key: &Key) -> Result<()> {
+    pub fn set_exists(&mut self, policy: &BasePolicy, key: &Key) -> Result<()> {
         self.begin();
         let mut field_count = self.estimate_key_size(key, false);
         let filter_size = self.estimate_filter_size(policy.filter_expression());
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy.txn.as_ref().map(|txn| txn.fields(key, false));
+        field_count += self.estimate_txn_size(txn.as_ref());
 
         self.size_buffer()?;
-        self.write_header(
-            &policy.base_policy,
-            INFO1_READ | INFO1_NOBINDATA,
-            0,
-            field_count,
-            0,
-        );
+        self.write_header(policy, INFO1_READ | INFO1_NOBINDATA, 0, field_count, 0);
         self.write_key(key, false);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -274,6 +306,8 @@ impl Buffer {
                 if filter_size > 0 {
                     field_count += 1;
                 }
+                let txn = policy.txn.as_ref().map(|txn| txn.fields(key, false));
+                field_count += self.estimate_txn_size(txn.as_ref());
                 for bin_name in bin_names {
                     self.estimate_operation_size_for_bin_name(bin_name);
                 }
@@ -281,6 +315,7 @@ impl Buffer {
                 self.size_buffer()?;
                 self.write_header(policy, INFO1_READ, 0, field_count, bin_names.len() as u16);
                 self.write_key(key, false);
+                self.write_txn(txn.as_ref());
 
                 if let Some(filter) = policy.filter_expression() {
                     self.write_filter_expression(filter, filter_size);
@@ -304,11 +339,14 @@ impl Buffer {
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy.txn.as_ref().map(|txn| txn.fields(key, false));
+        field_count += self.estimate_txn_size(txn.as_ref());
 
         self.estimate_operation_size_for_bin_name("");
         self.size_buffer()?;
         self.write_header(policy, INFO1_READ | INFO1_NOBINDATA, 0, field_count, 1);
         self.write_key(key, false);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -327,10 +365,13 @@ impl Buffer {
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy.txn.as_ref().map(|txn| txn.fields(key, false));
+        field_count += self.estimate_txn_size(txn.as_ref());
 
         self.size_buffer()?;
         self.write_header(policy, INFO1_READ | INFO1_GET_ALL, 0, field_count, 0);
         self.write_key(key, false);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -517,6 +558,12 @@ impl Buffer {
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy
+            .base_policy
+            .txn
+            .as_ref()
+            .map(|txn| txn.fields(key, write_attr != 0));
+        field_count += self.estimate_txn_size(txn.as_ref());
         self.size_buffer()?;
 
         if write_attr == 0 {
@@ -537,6 +584,7 @@ impl Buffer {
             );
         }
         self.write_key(key, policy.send_key && write_attr != 0);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -565,10 +613,17 @@ impl Buffer {
         if filter_size > 0 {
             field_count += 1;
         }
+        let txn = policy
+            .base_policy
+            .txn
+            .as_ref()
+            .map(|txn| txn.fields(key, true));
+        field_count += self.estimate_txn_size(txn.as_ref());
         self.size_buffer()?;
 
         self.write_header(&policy.base_policy, 0, INFO2_WRITE, field_count, 0);
         self.write_key(key, policy.send_key);
+        self.write_txn(txn.as_ref());
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size);
@@ -581,6 +636,88 @@ impl Buffer {
         Ok(())
     }
 
+    // Writes the command to verify the version of a record read in a transaction
+    pub fn set_txn_verify(&mut self, policy: &BasePolicy, key: &Key, version: u64) -> Result<()> {
+        self.begin();
+        let field_count = self.estimate_key_size(key, false) + 1;
+        self.data_offset += (RECORD_VERSION_SIZE + FIELD_HEADER_SIZE) as usize;
+
+        self.size_buffer()?;
+        self.write_header(policy, INFO1_READ | INFO1_NOBINDATA, 0, field_count, 0);
+        self.data_buffer[11] = INFO3_SC_READ_TYPE;
+        self.data_buffer[12] = INFO4_MRT_VERIFY_READ;
+        self.write_key(key, false);
+        self.write_field_version(version);
+        self.end();
+        Ok(())
+    }
+
+    // Writes the command to roll a record written in a transaction forward or back
+    pub fn set_txn_roll(
+        &mut self,
+        policy: &BasePolicy,
+        key: &Key,
+        txn: &Txn,
+        forward: bool,
+    ) -> Result<()> {
+        self.begin();
+        let fields = txn.fields(key, false);
+        let field_count =
+            self.estimate_key_size(key, false) + self.estimate_txn_size(Some(&fields));
+
+        self.size_buffer()?;
+        self.write_header(
+            policy,
+            0,
+            INFO2_WRITE | INFO2_DURABLE_DELETE,
+            field_count,
+            0,
+        );
+        self.data_buffer[12] = if forward {
+            INFO4_MRT_ROLL_FORWARD
+        } else {
+            INFO4_MRT_ROLL_BACK
+        };
+        self.write_key(key, false);
+        self.write_txn(Some(&fields));
+        self.end();
+        Ok(())
+    }
+
+    // Writes the command to mark a transaction monitor record for roll-forward
+    pub fn set_txn_mark_roll_forward(&mut self, policy: &BasePolicy, key: &Key) -> Result<()> {
+        let bin = Bin::new("fwd", Value::Bool(true));
+
+        self.begin();
+        let field_count = self.estimate_key_size(key, false);
+        self.estimate_operation_size_for_bin(&bin);
+
+        self.size_buffer()?;
+        self.write_header(policy, 0, INFO2_WRITE, field_count, 1);
+        self.write_key(key, false);
+        self.write_operation_for_bin(&bin, OperationType::Write);
+        self.end();
+        Ok(())
+    }
+
+    // Writes the command to remove a transaction monitor record
+    pub fn set_txn_close(&mut self, policy: &BasePolicy, key: &Key) -> Result<()> {
+        self.begin();
+        let field_count = self.estimate_key_size(key, false);
+
+        self.size_buffer()?;
+        self.write_header(
+            policy,
+            0,
+            INFO2_WRITE | INFO2_DELETE | INFO2_DURABLE_DELETE,
+            field_count,
+            0,
+        );
+        self.write_key(key, false);
+        self.end();
+        Ok(())
+    }
+
     pub fn set_scan(
         &mut self,
         policy: &ScanPolicy,
@@ -710,6 +847,7 @@ impl Buffer {
 
         let mut field_count = 0;
         let mut filter_size = 0;
+        let mut ctx_size = 0;
         let mut bin_name_size = 0;
 
         if !statement.namespace.is_empty() {
@@ -744,6 +882,12 @@ impl Buffer {
             self.data_offset += filter_size + FIELD_HEADER_SIZE as usize;
             field_count += 1;
 
+            ctx_size = filter.estimate_context_size();
+            if ctx_size > 0 {
+                self.data_offset += ctx_size + FIELD_HEADER_SIZE as usize;
+                field_count += 1;
+            }
+
             if let Bins::Some(ref bin_names) = statement.bins {
                 self.data_offset += FIELD_HEADER_SIZE as usize;
                 bin_name_size += 1;
@@ -843,6 +987,11 @@ impl Buffer {
 
             filter.write(self);
 
+            if ctx_size > 0 {
+                self.write_field_header(ctx_size, FieldType::IndexContext);
+                filter.write_context(self);
+            }
+
             if let Bins::Some(ref bin_names) = statement.bins {
                 if !bin_names.is_empty() {
                     self.write_field_header(bin_name_size, FieldType::QueryBinList);
@@ -935,6 +1084,27 @@ impl Buffer {
         field_count
     }
 
+    fn estimate_txn_size(&mut self, txn: Option<&TxnFields>) -> u16 {
+        let mut field_count: u16 = 0;
+
+        if let Some(txn) = txn {
+            self.data_offset += 8 + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+
+            if txn.version.is_some() {
+                self.data_offset += (RECORD_VERSION_SIZE + FIELD_HEADER_SIZE) as usize;
+                field_count += 1;
+            }
+
+            if txn.deadline.is_some() {
+                self.data_offset += 4 + FIELD_HEADER_SIZE as usize;
+                field_count += 1;
+            }
+        }
+
+        field_count
+    }
+
     fn estimate_args_size(&mut self, args: Option<&[Value]>) {
         if let Some(args) = args {
             self.data_offset += encoder::pack_array(&mut None, args) + FIELD_HEADER_SIZE as usize;
@@ -1088,6 +1258,41 @@ impl Buffer {
         }
     }
 
+    fn write_txn(&mut self, txn: Option<&TxnFields>) {
+        if let Some(txn) = txn {
+            self.write_field_header(8, FieldType::MrtId);
+            LittleEndian::write_i64(
+                &mut self.data_buffer[self.data_offset..self.data_offset + 8],
+                txn.id,
+            );
+            self.data_offset += 8;
+
+            if let Some(version) = txn.version {
+                self.write_field_version(version);
+            }
+
+            if let Some(deadline) = txn.deadline {
+                self.write_field_header(4, FieldType::MrtDeadline);
+                LittleEndian::write_u32(
+                    &mut self.data_buffer[self.data_offset..self.data_offset + 4],
+                    deadline,
+                );
+                self.data_offset += 4;
+            }
+        }
+    }
+
+    fn write_field_version(&mut self, version: u64) {
+        let size = RECORD_VERSION_SIZE as usize;
+        self.write_field_header(size, FieldType::RecordVersion);
+        LittleEndian::write_uint(
+            &mut self.data_buffer[self.data_offset..self.data_offset + size],
+            version,
+            size,
+        );
+        self.data_offset += size;
+    }
+
     fn write_filter_expression(&mut self, filter: &FilterExpression, size: usize) {
         self.write_field_header(size, FieldType::FilterExp);
         filter.pack(&mut Some(self));
@@ -1305,6 +1510,44 @@ impl Buffer {
         val
     }
 
+    // Reads the response fields, returning the record version and the transaction deadline if
+    // they were sent. Other fields are skipped.
+    pub fn read_txn_fields(&mut self, field_count: usize) -> Result<(Option<u64>, Option<u32>)> {
+        let mut version = None;
+        let mut deadline = None;
+
+        for _ in 0..field_count {
+            let data_offset = self.data_offset + 5;
+            let Some(header) = self.data_buffer.get(self.data_offset..data_offset) else {
+                bail!(ErrorKind::BadResponse(
+                    "Truncated response field".to_string()
+                ));
+            };
+            let field_size = NetworkEndian::read_u32(header) as usize;
+            let field_type = header[4];
+            let data = field_size
+                .checked_sub(1)
+                .and_then(|size| self.data_buffer.get(data_offset..data_offset + size));
+            let Some(data) = data else {
+                bail!(ErrorKind::BadResponse(format!(
+                    "Invalid response field size: {}",
+                    field_size
+                )));
+            };
+
+            if field_type == FieldType::RecordVersion as u8
+                && data.len() == RECORD_VERSION_SIZE as usize
+            {
+                version = Some(LittleEndian::read_uint(data, data.len()));
+            } else if field_type == FieldType::MrtDeadline as u8 && data.len() == 4 {
+                deadline = Some(LittleEndian::read_u32(data));
+            }
+            self.data_offset = data_offset + data.len();
+        }
+
+        Ok((version, deadline))
+    }
+
     pub fn write_u8(&mut self, val: u8) -> usize {
         self.data_buffer[self.data_offset] = val;
         self.data_offset += 1;
@@ -1418,3 +1661,46 @@ impl Buffer {
         println!(">>>>>>>>>>>>>>> {:?}", self.data_buffer.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Buffer;
+    use crate::txn::TxnFields;
+
+    #[test]
+    fn txn_fields() {
+        let fields = TxnFields {
+            id: 42,
+            version: Some(0x0001_0203_0405_0607),
+            deadline: Some(1000),
+        };
+
+        let mut buffer = Buffer::new(1024);
+        let field_count = buffer.estimate_txn_size(Some(&fields));
+        assert_eq!(field_count, 3);
+        let size = buffer.data_offset();
+        buffer.size_buffer().unwrap();
+
+        buffer.reset_offset();
+        buffer.write_txn(Some(&fields));
+        assert_eq!(buffer.data_offset(), size);
+
+        buffer.reset_offset();
+        let (version, deadline) = buffer.read_txn_fields(field_count as usize).unwrap();
+        assert_eq!(version, fields.version);
+        assert_eq!(deadline, fields.deadline);
+
+        // truncated field header
+        buffer.reset_offset();
+        buffer.data_buffer.truncate(3);
+        assert!(buffer.read_txn_fields(1).is_err());
+
+        // field size beyond the end of the buffer, and a size of zero
+        for size in [100u32, 0] {
+            buffer.data_buffer = size.to_be_bytes().to_vec();
+            buffer.data_buffer.extend_from_slice(&[0, 1, 2, 3]);
+            buffer.reset_offset();
+            assert!(buffer.read_txn_fields(1).is_err());
+        }
+    }
+}
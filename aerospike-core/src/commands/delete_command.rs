@@ -16,7 +16,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::cluster::{Cluster, Node};
-use crate::commands::{buffer, Command, SingleCommand};
+use crate::commands::{buffer, txn_command, Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::policy::WritePolicy;
@@ -38,7 +38,9 @@ impl<'a> DeleteCommand<'a> {
     }
 
     pub async fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self).await
+        let cluster = self.single_command.cluster();
+        let key = self.single_command.key;
+        txn_command::execute_write(self.policy, &cluster, key, true, self).await
     }
 }
 
@@ -80,13 +82,23 @@ impl<'a> Command for DeleteCommand<'a> {
         // A number of these are commented out because we just don't care enough to read
         // that section of the header. If we do care, uncomment and check!
         let result_code = ResultCode::from(conn.buffer.read_u8(Some(13)));
+        let field_count = conn.buffer.read_u16(Some(26)) as usize;
+        SingleCommand::empty_socket(conn).await?;
+
+        if let Some(ref txn) = self.policy.base_policy.txn {
+            let (version, _) = conn.buffer.read_txn_fields(field_count)?;
+            txn.on_write(
+                self.single_command.key,
+                version,
+                result_code == ResultCode::Ok,
+            );
+        }
 
         if result_code != ResultCode::Ok && result_code != ResultCode::KeyNotFoundError {
             bail!(ErrorKind::ServerError(result_code));
         }
 
         self.existed = result_code == ResultCode::Ok;
-
-        SingleCommand::empty_socket(conn).await
+        Ok(())
     }
 }
@@ -17,7 +17,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::cluster::{Cluster, Node};
-use crate::commands::{Command, ReadCommand, SingleCommand};
+use crate::commands::{txn_command, Command, ReadCommand};
 use crate::errors::Result;
 use crate::net::Connection;
 use crate::policy::WritePolicy;
@@ -41,7 +41,14 @@ impl<'a> ExecuteUDFCommand<'a> {
         args: Option<&'a [Value]>,
     ) -> Self {
         ExecuteUDFCommand {
-            read_command: ReadCommand::new(&policy.base_policy, cluster, key, Bins::All, crate::policy::Replica::Master),
+            read_command: ReadCommand::new(
+                &policy.base_policy,
+                cluster,
+                key,
+                Bins::All,
+                crate::policy::Replica::Master,
+            )
+            .for_write(),
             policy,
             package_name,
             function_name,
@@ -50,7 +57,9 @@ impl<'a> ExecuteUDFCommand<'a> {
     }
 
     pub async fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self).await
+        let cluster = self.read_command.single_command.cluster();
+        let key = self.read_command.single_command.key;
+        txn_command::execute_write(self.policy, &cluster, key, true, self).await
     }
 }
 
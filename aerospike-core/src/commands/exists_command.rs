@@ -19,25 +19,33 @@ use crate::cluster::{Cluster, Node};
 use crate::commands::{buffer, Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
-use crate::policy::WritePolicy;
+use crate::policy::{BasePolicy, Replica};
 use crate::{Key, ResultCode};
 
 pub struct ExistsCommand<'a> {
-    single_command: SingleCommand<'a>,
-    policy: &'a WritePolicy,
+    pub single_command: SingleCommand<'a>,
+    policy: &'a BasePolicy,
     pub exists: bool,
 }
 
 impl<'a> ExistsCommand<'a> {
-    pub fn new(policy: &'a WritePolicy, cluster: Arc<Cluster>, key: &'a Key) -> Self {
+    pub fn new(
+        policy: &'a BasePolicy,
+        cluster: Arc<Cluster>,
+        key: &'a Key,
+        replica: Replica,
+    ) -> Self {
         ExistsCommand {
-            single_command: SingleCommand::new(cluster, key, crate::policy::Replica::Master),
+            single_command: SingleCommand::new(cluster, key, replica),
             policy,
             exists: false,
         }
     }
 
     pub async fn execute(&mut self) -> Result<()> {
+        if let Some(ref txn) = self.policy.txn {
+            txn.verify_command(self.single_command.key)?;
+        }
         SingleCommand::execute(self.policy, self).await
     }
 }
@@ -80,13 +88,19 @@ impl<'a> Command for ExistsCommand<'a> {
         // A number of these are commented out because we just don't care enough to read
         // that section of the header. If we do care, uncomment and check!
         let result_code = ResultCode::from(conn.buffer.read_u8(Some(13)));
+        let field_count = conn.buffer.read_u16(Some(26)) as usize;
+        SingleCommand::empty_socket(conn).await?;
+
+        if let Some(ref txn) = self.policy.txn {
+            let (version, _) = conn.buffer.read_txn_fields(field_count)?;
+            txn.on_read(self.single_command.key, version);
+        }
 
         if result_code != ResultCode::Ok && result_code != ResultCode::KeyNotFoundError {
             bail!(ErrorKind::ServerError(result_code));
         }
 
         self.exists = result_code == ResultCode::Ok;
-
-        SingleCommand::empty_socket(conn).await
+        Ok(())
     }
 }
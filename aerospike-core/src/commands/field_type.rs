@@ -20,17 +20,17 @@ pub enum FieldType {
     Namespace = 0,
     Table = 1,
     Key = 2,
-    // BIN  = 3,
+    RecordVersion = 3,
     DigestRipe = 4,
-    // GUID  = 5,
-    // DigestRipeArray = 6,
+    MrtId = 5,
+    MrtDeadline = 6,
     TranId = 7, // user supplied transaction id, which is simply passed back,
     // ScanOptions = 8,
     ScanTimeout = 9,
     PIDArray = 11,
     IndexName = 21,
     IndexRange = 22,
-    // IndexFilter = 23,
+    IndexContext = 23,
     // IndexLimit = 24,
     // IndexOrderBy = 25,
     IndexType = 26,
@@ -27,6 +27,7 @@ pub mod scan_command;
 pub mod single_command;
 pub mod stream_command;
 pub mod touch_command;
+pub mod txn_command;
 pub mod write_command;
 
 mod field_type;
@@ -74,3 +75,17 @@ pub const fn keep_connection(err: &Error) -> bool {
         Error(ErrorKind::ServerError(ResultCode::KeyNotFoundError), _)
     )
 }
+
+// Errors that point at a problem with the node or the network path to it, rather than with the
+// command itself. These count towards the node's error rate.
+pub const fn is_node_error(err: &Error) -> bool {
+    matches!(
+        err,
+        Error(
+            ErrorKind::Connection(_)
+                | ErrorKind::Io(_)
+                | ErrorKind::ServerError(ResultCode::Timeout | ResultCode::DeviceOverload),
+            _
+        )
+    )
+}
@@ -16,7 +16,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::cluster::{Cluster, Node};
-use crate::commands::{Command, SingleCommand};
+use crate::commands::{txn_command, Command, SingleCommand};
 use crate::errors::Result;
 use crate::net::Connection;
 use crate::operations::Operation;
@@ -29,6 +29,7 @@ pub struct OperateCommand<'a> {
     pub record: OperateRecord,
     policy: &'a WritePolicy,
     operations: &'a [Operation<'a>],
+    has_write: bool,
 }
 
 /// The return value from operate. Like a record, but retains the order and duplicate keys of bins.
@@ -60,26 +61,22 @@ impl<'a> OperateCommand<'a> {
             record: OperateRecord::default(),
             policy,
             operations,
+            has_write: operations.iter().any(Operation::is_write),
         }
     }
 
     pub async fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self).await
+        let cluster = self.single_command.cluster();
+        let key = self.single_command.key;
+        let has_write = self.has_write;
+        txn_command::execute_write(self.policy, &cluster, key, has_write, self).await
     }
 
     fn parse_record(
         &mut self,
         conn: &mut Connection,
         op_count: usize,
-        field_count: usize,
     ) -> Result<()> {
-        // There can be fields in the response (setname etc). For now, ignore them. Expose them to
-        // the API if needed in the future.
-        for _ in 0..field_count {
-            let field_size = conn.buffer.read_u32(None) as usize;
-            conn.buffer.skip(4 + field_size);
-        }
-
         self.record.bins.reserve_exact(op_count);
         for _ in 0..op_count {
             let op_size = conn.buffer.read_u32(None) as usize;
@@ -96,7 +93,6 @@ impl<'a> OperateCommand<'a> {
 
         Ok(())
     }
-
 }
 
 #[async_trait::async_trait]
@@ -153,13 +149,29 @@ impl<'a> Command for OperateCommand<'a> {
             }
         }
 
+        // There can be other fields in the response (setname etc). For now, ignore them. Expose
+        // them to the API if needed in the future.
+        let (version, _) = conn.buffer.read_txn_fields(field_count)?;
+        if let Some(ref txn) = self.policy.base_policy.txn {
+            let key = self.single_command.key;
+            if self.has_write {
+                txn.on_write(
+                    key,
+                    version,
+                    ResultCode::from(result_code) == ResultCode::Ok,
+                );
+            } else {
+                txn.on_read(key, version);
+            }
+        }
+
         match ResultCode::from(result_code) {
             ResultCode::Ok => {
-                self.parse_record(conn, op_count, field_count)
+                self.parse_record(conn, op_count)
             }
             ResultCode::UdfBadResponse => {
                 // record bin "FAILURE" contains details about the UDF error
-                self.parse_record(conn, op_count, field_count)?;
+                self.parse_record(conn, op_count)?;
                 let reason = self.record
                     .bins.iter().find(|(k, _)|k == "FAILURE").map(|(_, v)|v)
                     .map_or(String::from("UDF Error"), ToString::to_string);
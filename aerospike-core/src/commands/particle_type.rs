@@ -34,7 +34,7 @@ pub enum ParticleType {
     // RTA_LIST        = 14,
     // RTA_DICT        = 15,
     // RTA_APPEND_DICT = 16,
-    // RTA_APPEND_LIST = 17,
+    BOOL = 17,
     // LUA_BLOB        = 18,
     HLL = 18,
     MAP = 19,
@@ -63,7 +63,7 @@ impl From<u8> for ParticleType {
             // 14 => ParticleType::RTA_LIST       ,
             // 15 => ParticleType::RTA_DICT       ,
             // 16 => ParticleType::RTA_APPEND_DICT,
-            // 17 => ParticleType::RTA_APPEND_LIST,
+            17 => ParticleType::BOOL,
             // 18 => ParticleType::LUA_BLOB       ,
             18 => ParticleType::HLL,
             19 => ParticleType::MAP,
@@ -24,26 +24,55 @@ use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::policy::{BasePolicy, Replica};
 use crate::value::bytes_to_particle;
-use crate::{Bins, Key, Record, ResultCode, Value};
+use crate::{Bins, Key, Record, RecordRef, ResultCode, Value};
+
+// Callback that is passed the record while it is still in the receive buffer.
+pub type RecordVisitor<'a> = dyn Fn(RecordRef<'_>) + Send + Sync + 'a;
 
 pub struct ReadCommand<'a> {
     pub single_command: SingleCommand<'a>,
     pub record: Option<Record>,
     policy: &'a BasePolicy,
     bins: Bins,
+    visitor: Option<&'a RecordVisitor<'a>>,
+    write: bool,
 }
 
 impl<'a> ReadCommand<'a> {
-    pub fn new(policy: &'a BasePolicy, cluster: Arc<Cluster>, key: &'a Key, bins: Bins, replica: Replica) -> Self {
+    pub fn new(
+        policy: &'a BasePolicy,
+        cluster: Arc<Cluster>,
+        key: &'a Key,
+        bins: Bins,
+        replica: Replica,
+    ) -> Self {
         ReadCommand {
             single_command: SingleCommand::new(cluster, key, replica),
             bins,
             policy,
             record: None,
+            visitor: None,
+            write: false,
         }
     }
 
+    // Passes the record to the visitor instead of decoding it into `record`.
+    pub fn with_visitor(mut self, visitor: &'a RecordVisitor<'a>) -> Self {
+        self.visitor = Some(visitor);
+        self
+    }
+
+    // Marks the command as a write for the transaction of the policy. Used by UDF commands, whose
+    // results are parsed like those of reads.
+    pub fn for_write(mut self) -> Self {
+        self.write = true;
+        self
+    }
+
     pub async fn execute(&mut self) -> Result<()> {
+        if let Some(ref txn) = self.policy.txn {
+            txn.verify_command(self.single_command.key)?;
+        }
         SingleCommand::execute(self.policy, self).await
     }
 
@@ -51,19 +80,11 @@ impl<'a> ReadCommand<'a> {
         &mut self,
         conn: &mut Connection,
         op_count: usize,
-        field_count: usize,
         generation: u32,
         expiration: u32,
     ) -> Result<Record> {
         let mut bins: HashMap<String, Value> = HashMap::with_capacity(op_count);
 
-        // There can be fields in the response (setname etc). For now, ignore them. Expose them to
-        // the API if needed in the future.
-        for _ in 0..field_count {
-            let field_size = conn.buffer.read_u32(None) as usize;
-            conn.buffer.skip(4 + field_size);
-        }
-
         for _ in 0..op_count {
             let op_size = conn.buffer.read_u32(None) as usize;
             conn.buffer.skip(1);
@@ -146,20 +167,46 @@ impl<'a> Command for ReadCommand<'a> {
             }
         }
 
+        // There can be other fields in the response (setname etc). For now, ignore them. Expose
+        // them to the API if needed in the future.
+        let (version, _) = conn.buffer.read_txn_fields(field_count)?;
+        if let Some(ref txn) = self.policy.txn {
+            let key = self.single_command.key;
+            if self.write {
+                txn.on_write(
+                    key,
+                    version,
+                    ResultCode::from(result_code) == ResultCode::Ok,
+                );
+            } else {
+                txn.on_read(key, version);
+            }
+        }
+
         match ResultCode::from(result_code) {
             ResultCode::Ok => {
+                if let Some(visitor) = self.visitor {
+                    let data = &conn.buffer.data_buffer[..receive_size];
+                    visitor(RecordRef::new(
+                        data,
+                        field_count,
+                        op_count,
+                        generation,
+                        expiration,
+                    )?);
+                    return Ok(());
+                }
                 let record = if self.bins.is_none() {
                     Record::new(None, HashMap::new(), generation, expiration)
                 } else {
-                    self.parse_record(conn, op_count, field_count, generation, expiration)?
+                    self.parse_record(conn, op_count, generation, expiration)?
                 };
                 self.record = Some(record);
                 Ok(())
             }
             ResultCode::UdfBadResponse => {
                 // record bin "FAILURE" contains details about the UDF error
-                let record =
-                    self.parse_record(conn, op_count, field_count, generation, expiration)?;
+                let record = self.parse_record(conn, op_count, generation, expiration)?;
                 let reason = record
                     .bins
                     .get("FAILURE")
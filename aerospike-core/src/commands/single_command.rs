@@ -18,12 +18,14 @@ use std::sync::{Arc, Weak};
 use crate::cluster::partition::Partition;
 use crate::cluster::{Cluster, Node};
 use crate::commands::{self};
-use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
 use crate::net::Connection;
 use crate::policy::Policy;
 use crate::Key;
 use aerospike_rt::sleep;
-use aerospike_rt::time::Instant;
+use aerospike_rt::time::{Duration, Instant};
+use futures::future::{select, Either};
+use futures::Future;
 
 pub struct SingleCommand<'a> {
     cluster: Arc<Cluster>,
@@ -44,6 +46,43 @@ pub async fn try_with_timeout<O, F: futures::Future<Output = Result<O>>> (deadli
     }
 }
 
+// Runs the `primary` request and, if it has not completed within `hedge_delay`, starts the
+// `hedge` request concurrently. The first successful response wins and the other request is
+// cancelled. If one request fails, the other one is awaited instead; the first error is returned
+// only if both fail. `hedge` returns `None` if there is no other node to send the request to.
+pub async fn try_hedged<O, P, H, F>(
+    hedge_delay: Option<Duration>,
+    primary: P,
+    hedge: H,
+) -> Result<O>
+where
+    P: Future<Output = Result<O>>,
+    H: FnOnce() -> Option<F>,
+    F: Future<Output = Result<O>>,
+{
+    let Some(hedge_delay) = hedge_delay else {
+        return primary.await;
+    };
+
+    futures::pin_mut!(primary);
+    let delay = sleep(hedge_delay);
+    futures::pin_mut!(delay);
+    let primary = match select(primary, delay).await {
+        Either::Left((result, _)) => return result,
+        Either::Right(((), primary)) => primary,
+    };
+
+    let Some(hedge) = hedge() else {
+        return primary.await;
+    };
+    futures::pin_mut!(hedge);
+    match select(primary, hedge).await {
+        Either::Left((Err(err), other)) => other.await.map_err(|_| err),
+        Either::Right((Err(err), other)) => other.await.map_err(|_| err),
+        Either::Left((result, _)) | Either::Right((result, _)) => result,
+    }
+}
+
 impl<'a> SingleCommand<'a> {
     pub fn new(cluster: Arc<Cluster>, key: &'a Key, replica: crate::policy::Replica,) -> Self {
         let partition = Partition::new_by_key(key);
@@ -56,6 +95,32 @@ impl<'a> SingleCommand<'a> {
         }
     }
 
+    // Makes the first attempt go to the node after the preferred one in the replica sequence.
+    // Used by hedged requests, which should not hit the same node as the original request.
+    // Returns false if there is no other node, e.g. with a replication factor of 1.
+    pub fn skip_preferred_node(&mut self) -> bool {
+        let Ok(preferred) = self
+            .cluster
+            .get_node(&self.partition, self.replica, Weak::new())
+        else {
+            return false;
+        };
+        match self
+            .cluster
+            .get_node(&self.partition, self.replica, Arc::downgrade(&preferred))
+        {
+            Ok(next) if !Arc::ptr_eq(&next, &preferred) => {
+                self.last_tried = Arc::downgrade(&preferred);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn cluster(&self) -> Arc<Cluster> {
+        self.cluster.clone()
+    }
+
     pub fn get_node(&mut self) -> Result<Arc<Node>> {
         let this_time = self.cluster.get_node(&self.partition, self.replica, self.last_tried.clone())?;
         self.last_tried = Arc::downgrade(&this_time);
@@ -121,12 +186,19 @@ impl<'a> SingleCommand<'a> {
             // set command node, so when you return a record it has the node
             let node = match cmd.get_node() {
                 Ok(node) => node,
+                // All candidate nodes exceeded their max. error rate. Fail fast.
+                Err(err @ Error(ErrorKind::MaxErrorRate(_), _)) => return Err(err),
                 Err(_) => continue, // Node is currently inactive. Retry.
             };
 
-            let mut conn = match try_with_timeout(deadline, node.get_connection()).await {
+            let mut conn = match try_with_timeout(deadline, node.get_connection_with_deadline(deadline)).await {
                 Ok(conn) => conn,
+                // Waiting for a free connection already used up the allowed time.
+                Err(err @ Error(ErrorKind::ConnectionWaitTimeout(_), _)) => return Err(err),
                 Err(err) => {
+                    if commands::is_node_error(&err) {
+                        node.add_error();
+                    }
                     warn!("Node {}: {}", node, err);
                     continue;
                 }
@@ -140,25 +212,33 @@ impl<'a> SingleCommand<'a> {
                 .chain_err(|| "Failed to set timeout for send buffer")?;
 
             // Send command.
+            conn.set_busy(true);
             if let Err(err) = try_with_timeout(deadline, cmd.write_buffer(&mut conn)).await {
                 // IO errors are considered temporary anomalies. Retry.
                 // Close socket to flush out possible garbage. Do not put back in pool.
                 conn.invalidate();
+                node.add_error();
                 warn!("Node {}: {}", node, err);
                 continue;
             }
 
             // Parse results.
             if let Err(err) = try_with_timeout(deadline, cmd.parse_result(&mut conn)).await {
+                if commands::is_node_error(&err) {
+                    node.add_error();
+                }
                 // close the connection
                 // cancelling/closing the batch/multi commands will return an error, which will
                 // close the connection to throw away its data and signal the server about the
                 // situation. We will not put back the connection in the buffer.
-                if !commands::keep_connection(&err) {
+                if commands::keep_connection(&err) {
+                    conn.set_busy(false);
+                } else {
                     conn.invalidate();
                 }
                 return Err(err);
             } else {
+                conn.set_busy(false);
                 return Ok(());
             }
         }
@@ -166,3 +246,66 @@ impl<'a> SingleCommand<'a> {
         bail!(ErrorKind::Connection("Timeout".to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::try_hedged;
+    use crate::errors::{Error, ErrorKind, Result};
+    use aerospike_rt::sleep;
+    use aerospike_rt::time::Duration;
+    use futures::future::Ready;
+
+    async fn respond(delay_ms: u64, result: Result<u8>) -> Result<u8> {
+        sleep(Duration::from_millis(delay_ms)).await;
+        result
+    }
+
+    #[test]
+    fn hedged_requests() {
+        let rt = aerospike_rt::tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let delay = Some(Duration::from_millis(10));
+
+            // primary answers before the hedge delay; the hedge is never started
+            let res = try_hedged(delay, respond(0, Ok(1)), || {
+                Some(async { panic!("hedged") })
+            })
+            .await;
+            assert_eq!(res.unwrap(), 1);
+
+            // hedge answers first
+            let res = try_hedged(delay, respond(200, Ok(1)), || Some(respond(0, Ok(2)))).await;
+            assert_eq!(res.unwrap(), 2);
+
+            // primary fails with a node error after the hedge was started; wait for the hedge
+            let err = Err(ErrorKind::Connection("Timeout".to_string()).into());
+            let res = try_hedged(delay, respond(20, err), || Some(respond(40, Ok(2)))).await;
+            assert_eq!(res.unwrap(), 2);
+
+            // hedge fails fast because its node exceeded the error rate; wait for the primary
+            let err = Err(ErrorKind::MaxErrorRate("node B".to_string()).into());
+            let res = try_hedged(delay, respond(40, Ok(1)), || Some(respond(0, err))).await;
+            assert_eq!(res.unwrap(), 1);
+
+            // both fail; the first error is returned
+            let err1 = Err(ErrorKind::Connection("Timeout".to_string()).into());
+            let err2 = Err(ErrorKind::MaxErrorRate("node B".to_string()).into());
+            let res = try_hedged(delay, respond(40, err1), || Some(respond(0, err2))).await;
+            assert!(matches!(res, Err(Error(ErrorKind::MaxErrorRate(_), _))));
+
+            // no other node to hedge to
+            let res = try_hedged(delay, respond(20, Ok(1)), || None::<Ready<Result<u8>>>).await;
+            assert_eq!(res.unwrap(), 1);
+
+            // no hedging without a delay
+            let res = try_hedged(None, respond(20, Ok(1)), || {
+                Some(async { panic!("hedged") })
+            })
+            .await;
+            assert_eq!(res.unwrap(), 1);
+        });
+    }
+}
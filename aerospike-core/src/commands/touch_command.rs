@@ -17,7 +17,7 @@ use std::time::Duration;
 
 use crate::cluster::{Cluster, Node};
 use crate::commands::buffer;
-use crate::commands::{Command, SingleCommand};
+use crate::commands::{txn_command, Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::policy::WritePolicy;
@@ -37,7 +37,9 @@ impl<'a> TouchCommand<'a> {
     }
 
     pub async fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self).await
+        let cluster = self.single_command.cluster();
+        let key = self.single_command.key;
+        txn_command::execute_write(self.policy, &cluster, key, true, self).await
     }
 }
 
@@ -77,10 +79,21 @@ impl<'a> Command for TouchCommand<'a> {
         conn.buffer.reset_offset();
 
         let result_code = ResultCode::from(conn.buffer.read_u8(Some(13)));
+        let field_count = conn.buffer.read_u16(Some(26)) as usize;
+        SingleCommand::empty_socket(conn).await?;
+
+        if let Some(ref txn) = self.policy.base_policy.txn {
+            let (version, _) = conn.buffer.read_txn_fields(field_count)?;
+            txn.on_write(
+                self.single_command.key,
+                version,
+                result_code == ResultCode::Ok,
+            );
+        }
+
         if result_code != ResultCode::Ok {
             bail!(ErrorKind::ServerError(result_code));
         }
-
-        SingleCommand::empty_socket(conn).await
+        Ok(())
     }
 }
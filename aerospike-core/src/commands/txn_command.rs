@@ -0,0 +1,362 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+
+use crate::cluster::{Cluster, Node};
+use crate::commands::{buffer, Command, SingleCommand};
+use crate::errors::{Error, ErrorKind, Result};
+use crate::net::Connection;
+use crate::operations::lists::{ListOrderType, ListPolicy, ListWriteFlags};
+use crate::operations::{lists, scalar, Operation};
+use crate::policy::{BasePolicy, Expiration, Replica, WritePolicy};
+use crate::{AbortStatus, CommitError, CommitStatus, Key, ResultCode, Txn, TxnState, Value};
+
+// Commands of the multi-record transaction protocol. All of them go to the master node.
+pub enum TxnAction<'a> {
+    // Adds a key to the monitor record, creating the monitor record if needed.
+    AddKey(&'a [Operation<'a>]),
+    // Checks that the version of a record read in the transaction is unchanged.
+    Verify(u64),
+    // Marks the monitor record, so that the server rolls the transaction forward if the client
+    // does not complete the commit.
+    MarkRollForward,
+    // Rolls a record written in the transaction forward (`true`) or back.
+    Roll(bool),
+    // Removes the monitor record.
+    Close,
+}
+
+pub struct TxnCommand<'a> {
+    single_command: SingleCommand<'a>,
+    policy: &'a WritePolicy,
+    txn: &'a Txn,
+    action: TxnAction<'a>,
+}
+
+impl<'a> TxnCommand<'a> {
+    pub fn new(
+        policy: &'a WritePolicy,
+        cluster: Arc<Cluster>,
+        txn: &'a Txn,
+        key: &'a Key,
+        action: TxnAction<'a>,
+    ) -> Self {
+        TxnCommand {
+            single_command: SingleCommand::new(cluster, key, Replica::Master),
+            policy,
+            txn,
+            action,
+        }
+    }
+
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> Command for TxnCommand<'a> {
+    async fn write_timeout(
+        &mut self,
+        conn: &mut Connection,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        conn.buffer.write_timeout(timeout);
+        Ok(())
+    }
+
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
+    }
+
+    fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        let policy = &self.policy.base_policy;
+        let key = self.single_command.key;
+        match self.action {
+            TxnAction::AddKey(operations) => conn.buffer.set_operate(self.policy, key, operations),
+            TxnAction::Verify(version) => conn.buffer.set_txn_verify(policy, key, version),
+            TxnAction::MarkRollForward => conn.buffer.set_txn_mark_roll_forward(policy, key),
+            TxnAction::Roll(forward) => conn.buffer.set_txn_roll(policy, key, self.txn, forward),
+            TxnAction::Close => conn.buffer.set_txn_close(policy, key),
+        }
+    }
+
+    fn get_node(&mut self) -> Result<Arc<Node>> {
+        self.single_command.get_node()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        // Read header.
+        if let Err(err) = conn
+            .read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize)
+            .await
+        {
+            warn!("Parse result error: {}", err);
+            return Err(err);
+        }
+
+        conn.buffer.reset_offset();
+        let result_code = ResultCode::from(conn.buffer.read_u8(Some(13)));
+        let field_count = conn.buffer.read_u16(Some(26)) as usize;
+        SingleCommand::empty_socket(conn).await?;
+        let (_, deadline) = conn.buffer.read_txn_fields(field_count)?;
+
+        match (&self.action, result_code) {
+            (TxnAction::AddKey(_), ResultCode::Ok) => {
+                if let Some(deadline) = deadline {
+                    self.txn.set_deadline(deadline);
+                }
+                Ok(())
+            }
+            (TxnAction::Verify(_) | TxnAction::Roll(_), ResultCode::Ok)
+            | (TxnAction::MarkRollForward, ResultCode::Ok | ResultCode::MrtCommitted)
+            | (TxnAction::Close, ResultCode::Ok | ResultCode::KeyNotFoundError) => Ok(()),
+            (_, rc) => bail!(ErrorKind::ServerError(rc)),
+        }
+    }
+}
+
+// Errors that leave it unknown whether the server applied the command.
+const fn is_in_doubt(err: &Error) -> bool {
+    !matches!(
+        err,
+        Error(
+            ErrorKind::ServerError(_)
+                | ErrorKind::UdfBadResponse(_)
+                | ErrorKind::InvalidArgument(_),
+            _
+        )
+    )
+}
+
+// Policy for the commands on the monitor record. Only the timeouts of the command policy apply.
+fn monitor_policy(policy: &BasePolicy, txn: &Txn) -> WritePolicy {
+    WritePolicy {
+        base_policy: BasePolicy {
+            timeout: policy.timeout,
+            max_retries: policy.max_retries,
+            sleep_between_retries: policy.sleep_between_retries,
+            ..BasePolicy::default()
+        },
+        expiration: Expiration::Seconds(txn.timeout()),
+        respond_per_each_op: true,
+        ..WritePolicy::default()
+    }
+}
+
+// Runs a single record write command. If the policy has a transaction, the key is added to the
+// transaction's monitor record before the command is sent.
+pub async fn execute_write(
+    policy: &WritePolicy,
+    cluster: &Arc<Cluster>,
+    key: &Key,
+    has_write: bool,
+    cmd: &mut (dyn Command + Send),
+) -> Result<()> {
+    let Some(ref txn) = policy.base_policy.txn else {
+        return SingleCommand::execute(policy, cmd).await;
+    };
+
+    txn.verify_command(key)?;
+    if !has_write {
+        return SingleCommand::execute(policy, cmd).await;
+    }
+
+    if !txn.is_written(key) {
+        add_key(&policy.base_policy, cluster, txn, key).await?;
+    }
+
+    let res = SingleCommand::execute(policy, cmd).await;
+    if let Err(ref err) = res {
+        if is_in_doubt(err) {
+            txn.on_write_in_doubt(key);
+        }
+    }
+    res
+}
+
+async fn add_key(policy: &BasePolicy, cluster: &Arc<Cluster>, txn: &Txn, key: &Key) -> Result<()> {
+    let monitor_key = txn.monitor_key()?;
+    let policy = monitor_policy(policy, txn);
+    let list_policy = ListPolicy::new_with_flags(
+        ListOrderType::Ordered,
+        [ListWriteFlags::AddUnique, ListWriteFlags::NoFail],
+    );
+    let id = as_bin!("id", txn.id());
+    let digest = Value::from(key.digest.to_vec());
+
+    let mut ops = Vec::with_capacity(2);
+    if !txn.monitor_exists() {
+        ops.push(scalar::put(&id));
+    }
+    ops.push(lists::append(&list_policy, "keyds", &digest));
+
+    let mut command = TxnCommand::new(
+        &policy,
+        cluster.clone(),
+        txn,
+        &monitor_key,
+        TxnAction::AddKey(&ops),
+    );
+    let res = command.execute().await;
+    if let Err(ref err) = res {
+        if is_in_doubt(err) {
+            txn.set_monitor_in_doubt();
+        }
+    }
+    res
+}
+
+async fn run_all(
+    policy: &WritePolicy,
+    cluster: &Arc<Cluster>,
+    txn: &Txn,
+    commands: Vec<(Key, TxnAction<'_>)>,
+) -> Result<()> {
+    let futures = commands.into_iter().map(|(key, action)| async move {
+        let mut command = TxnCommand::new(policy, cluster.clone(), txn, &key, action);
+        command.execute().await
+    });
+    join_all(futures).await.into_iter().collect()
+}
+
+async fn verify(policy: &WritePolicy, cluster: &Arc<Cluster>, txn: &Txn) -> Result<()> {
+    let commands = txn
+        .reads()
+        .into_iter()
+        .map(|(key, version)| (key, TxnAction::Verify(version)))
+        .collect();
+    run_all(policy, cluster, txn, commands).await
+}
+
+async fn roll(
+    policy: &WritePolicy,
+    cluster: &Arc<Cluster>,
+    txn: &Txn,
+    forward: bool,
+) -> Result<()> {
+    let commands = txn
+        .writes()
+        .into_iter()
+        .map(|key| (key, TxnAction::Roll(forward)))
+        .collect();
+    run_all(policy, cluster, txn, commands).await
+}
+
+async fn monitor_command(
+    policy: &WritePolicy,
+    cluster: &Arc<Cluster>,
+    txn: &Txn,
+    action: TxnAction<'_>,
+) -> Result<()> {
+    let monitor_key = txn.monitor_key()?;
+    let policy = monitor_policy(&policy.base_policy, txn);
+    let mut command = TxnCommand::new(&policy, cluster.clone(), txn, &monitor_key, action);
+    command.execute().await
+}
+
+pub async fn commit(
+    policy: &WritePolicy,
+    cluster: &Arc<Cluster>,
+    txn: &Txn,
+) -> Result<CommitStatus> {
+    match txn.state() {
+        TxnState::Open => {
+            if let Err(err) = verify(policy, cluster, txn).await {
+                txn.set_state(TxnState::Aborted);
+                let commit_err = if roll(policy, cluster, txn, false).await.is_err() {
+                    CommitError::VerifyFailAbortAbandoned
+                } else if txn.close_monitor()
+                    && monitor_command(policy, cluster, txn, TxnAction::Close)
+                        .await
+                        .is_err()
+                {
+                    CommitError::VerifyFailCloseAbandoned
+                } else {
+                    CommitError::VerifyFail
+                };
+                return Err(Error::with_chain(
+                    err,
+                    ErrorKind::TxnCommit(commit_err, false),
+                ));
+            }
+            txn.set_state(TxnState::Verified);
+        }
+        TxnState::Verified => (),
+        TxnState::Committed => return Ok(CommitStatus::AlreadyCommitted),
+        TxnState::Aborted => bail!(ErrorKind::ServerError(ResultCode::MrtAborted)),
+    }
+
+    if txn.monitor_might_exist() {
+        if let Err(err) = monitor_command(policy, cluster, txn, TxnAction::MarkRollForward).await {
+            let in_doubt = match err {
+                Error(ErrorKind::ServerError(ResultCode::MrtAborted), _) => {
+                    txn.set_state(TxnState::Aborted);
+                    false
+                }
+                ref err => is_in_doubt(err),
+            };
+            txn.set_in_doubt(in_doubt);
+            return Err(Error::with_chain(
+                err,
+                ErrorKind::TxnCommit(CommitError::MarkRollForwardAbandoned, in_doubt),
+            ));
+        }
+    }
+
+    txn.set_state(TxnState::Committed);
+    txn.set_in_doubt(false);
+
+    if roll(policy, cluster, txn, true).await.is_err() {
+        return Ok(CommitStatus::RollForwardAbandoned);
+    }
+
+    if txn.close_monitor()
+        && monitor_command(policy, cluster, txn, TxnAction::Close)
+            .await
+            .is_err()
+    {
+        return Ok(CommitStatus::CloseAbandoned);
+    }
+
+    Ok(CommitStatus::Ok)
+}
+
+pub async fn abort(policy: &WritePolicy, cluster: &Arc<Cluster>, txn: &Txn) -> Result<AbortStatus> {
+    match txn.state() {
+        TxnState::Open | TxnState::Verified => (),
+        TxnState::Committed => bail!(ErrorKind::ServerError(ResultCode::MrtCommitted)),
+        TxnState::Aborted => return Ok(AbortStatus::AlreadyAborted),
+    }
+
+    txn.set_state(TxnState::Aborted);
+
+    if roll(policy, cluster, txn, false).await.is_err() {
+        return Ok(AbortStatus::RollBackAbandoned);
+    }
+
+    if txn.close_monitor()
+        && monitor_command(policy, cluster, txn, TxnAction::Close)
+            .await
+            .is_err()
+    {
+        return Ok(AbortStatus::CloseAbandoned);
+    }
+
+    Ok(AbortStatus::Ok)
+}
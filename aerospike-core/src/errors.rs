@@ -52,7 +52,7 @@
 
 #![allow(missing_docs)]
 
-use crate::ResultCode;
+use crate::{CommitError, ResultCode};
 
 error_chain! {
 
@@ -109,12 +109,46 @@ error_chain! {
             display("Too many connections")
         }
 
+/// Timed out waiting for a pooled connection to become available after the max. number of
+/// connections per node was reached.
+        ConnectionWaitTimeout(details: String) {
+            description("Timed out waiting for a connection")
+            display("Timed out waiting for a connection: {}", details)
+        }
+
+/// The node has exceeded the max. error rate configured in the client policy and is temporarily
+/// not accepting commands.
+        MaxErrorRate(details: String) {
+            description("Max error rate exceeded")
+            display("Max error rate exceeded: {}", details)
+        }
+
+/// A value could not be converted into the requested type, either because it is of a different
+/// type or because it is out of range for the requested type.
+        ValueConversion(expected: String, actual: String) {
+            description("Value conversion failed")
+            display("Cannot convert {} value to {}", actual, expected)
+        }
+
+/// The value of a record bin could not be converted into the requested type.
+        BinConversion(bin: String, expected: String, actual: String) {
+            description("Bin value conversion failed")
+            display("Cannot convert {} value of bin `{}` to {}", actual, bin, expected)
+        }
+
 /// Server responded with a response code indicating an error condition.
         ServerError(rc: ResultCode) {
             description("Server Error")
             display("Server error: {}", rc.into_string())
         }
 
+/// A transaction could not be committed. If `in_doubt` is set, it is unknown whether the
+/// transaction was committed; the commit can be retried to find out.
+        TxnCommit(err: CommitError, in_doubt: bool) {
+            description("Transaction commit failed")
+            display("Transaction commit failed: {}{}", err, if *in_doubt { " (in doubt)" } else { "" })
+        }
+
 /// Error returned when executing a User-Defined Function (UDF) resulted in an error.
         UdfBadResponse(details: String) {
             description("UDF Bad Response")
@@ -699,8 +699,11 @@ fn add_write(
 
 #[doc(hidden)]
 const fn get_value_type(return_type: i64) -> ExpType {
-    if (return_type & !(ListReturnType::Inverted as i64)) == ListReturnType::Values as i64 {
+    let t = return_type & !(ListReturnType::Inverted as i64);
+    if t == ListReturnType::Values as i64 {
         ExpType::LIST
+    } else if t == ListReturnType::Exists as i64 {
+        ExpType::BOOL
     } else {
         ExpType::INT
     }
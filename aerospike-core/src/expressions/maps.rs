@@ -861,8 +861,13 @@ const fn get_value_type(return_type: i64) -> ExpType {
     let t = return_type & !(MapReturnType::Inverted as i64);
     if t == MapReturnType::Key as i64 || t == MapReturnType::Value as i64 {
         ExpType::LIST
-    } else if t == MapReturnType::KeyValue as i64 {
+    } else if t == MapReturnType::KeyValue as i64
+        || t == MapReturnType::UnorderedMap as i64
+        || t == MapReturnType::OrderedMap as i64
+    {
         ExpType::MAP
+    } else if t == MapReturnType::Exists as i64 {
+        ExpType::BOOL
     } else {
         ExpType::INT
     }
@@ -23,9 +23,12 @@ pub mod regex_flag;
 use crate::commands::buffer::Buffer;
 use crate::msgpack::encoder::{pack_array_begin, pack_integer, pack_raw_string, pack_value};
 use crate::operations::cdt_context::CdtContext;
+use crate::geo::ToGeoJson;
 use crate::{ParticleType, Value};
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt::Debug;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Expression Data Types for usage in some `FilterExpressions` on for example Map and List
 #[derive(Debug, Clone, Copy)]
@@ -642,6 +645,15 @@ pub fn regex_compare(regex: String, flags: i64, bin: FilterExpression) -> Filter
 /// let region = "{\"type\": \"Polygon\", \"coordinates\": [ [[-122.500000, 37.000000],[-121.000000, 37.000000], [-121.000000, 38.080000],[-122.500000, 38.080000], [-122.500000, 37.000000]] ] }";
 /// geo_compare(geo_bin("a".to_string()), geo_val(region.to_string()));
 /// ```
+///
+/// Using typed geospatial values:
+/// ```
+/// use aerospike::expressions::{geo_compare, geo_bin, geo_val};
+/// use aerospike::{GeoCircle, GeoPoint};
+/// // Query points within 1km of the given point.
+/// let circle = GeoCircle::new(GeoPoint::new(-122.0, 37.5).unwrap(), 1000.0).unwrap();
+/// geo_compare(geo_bin("a".to_string()), geo_val(circle));
+/// ```
 pub fn geo_compare(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression::new(
         Some(ExpOp::Geo),
@@ -658,6 +670,44 @@ pub fn int_val(val: i64) -> FilterExpression {
     FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
 }
 
+/// Creates a time value, expressed as 64 bit integer nanoseconds since 1970-01-01 epoch, for
+/// comparison with `last_update` and `void_time`.
+/// ```
+/// use std::time::{Duration, SystemTime};
+/// use aerospike::expressions::{ge, last_update, time_val};
+/// // Record last updated within the last 24 hours
+/// ge(last_update(), time_val(SystemTime::now() - Duration::from_secs(24 * 60 * 60)));
+/// ```
+pub fn time_val<T: Into<SystemTime>>(val: T) -> FilterExpression {
+    let nanos = match val.into().duration_since(UNIX_EPOCH) {
+        Ok(d) => i64::try_from(d.as_nanos()).unwrap_or(i64::MAX),
+        Err(err) => i64::try_from(err.duration().as_nanos()).map_or(i64::MIN, |n| -n),
+    };
+    int_val(nanos)
+}
+
+/// Creates a duration value in integer milliseconds, for comparison with `since_update`.
+/// ```
+/// use std::time::Duration;
+/// use aerospike::expressions::{gt, millis_val, since_update};
+/// // Record last updated more than 2 hours ago
+/// gt(since_update(), millis_val(Duration::from_secs(2 * 60 * 60)));
+/// ```
+pub fn millis_val(val: Duration) -> FilterExpression {
+    int_val(i64::try_from(val.as_millis()).unwrap_or(i64::MAX))
+}
+
+/// Creates a duration value in integer seconds, for comparison with `ttl`.
+/// ```
+/// use std::time::Duration;
+/// use aerospike::expressions::{lt, secs_val, ttl};
+/// // Record expires in less than 1 hour
+/// lt(ttl(), secs_val(Duration::from_secs(60 * 60)));
+/// ```
+pub fn secs_val(val: Duration) -> FilterExpression {
+    int_val(i64::try_from(val.as_secs()).unwrap_or(i64::MAX))
+}
+
 /// Creates a Boolean value
 pub fn bool_val(val: bool) -> FilterExpression {
     FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
@@ -696,9 +746,17 @@ pub fn map_val(val: HashMap<Value, Value>) -> FilterExpression {
     FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
 }
 
-/// Create geospatial json string value.
-pub fn geo_val(val: String) -> FilterExpression {
-    FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
+/// Create geospatial json string value. The value can be given as a GeoJSON string or as one of
+/// the typed geospatial values, e.g. a `GeoPoint` or `GeoPolygon`.
+pub fn geo_val<T: ToGeoJson>(val: T) -> FilterExpression {
+    FilterExpression::new(
+        None,
+        Some(Value::GeoJSON(val.to_geojson())),
+        None,
+        None,
+        None,
+        None,
+    )
 }
 
 /// Create a Nil Value
@@ -0,0 +1,311 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use crate::errors::{ErrorKind, Result};
+use crate::Value;
+
+/// Types that serialize to a GeoJSON string, for use in geospatial query filters and
+/// expressions. Implemented for the typed geospatial values as well as for strings containing
+/// GeoJSON.
+pub trait ToGeoJson {
+    /// Returns the GeoJSON representation.
+    fn to_geojson(&self) -> String;
+}
+
+impl ToGeoJson for str {
+    fn to_geojson(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToGeoJson for String {
+    fn to_geojson(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: ToGeoJson + ?Sized> ToGeoJson for &T {
+    fn to_geojson(&self) -> String {
+        (**self).to_geojson()
+    }
+}
+
+/// Geographic point, given by longitude and latitude in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    lng: f64,
+    lat: f64,
+}
+
+impl GeoPoint {
+    /// Create a new point. Returns an error unless the longitude is within [-180, 180] and the
+    /// latitude is within [-90, 90] degrees.
+    pub fn new(lng: f64, lat: f64) -> Result<Self> {
+        if !(-180.0..=180.0).contains(&lng) {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Longitude out of range: {}",
+                lng
+            )));
+        }
+        if !(-90.0..=90.0).contains(&lat) {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Latitude out of range: {}",
+                lat
+            )));
+        }
+        Ok(GeoPoint { lng, lat })
+    }
+
+    /// Returns the longitude in degrees.
+    pub const fn lng(&self) -> f64 {
+        self.lng
+    }
+
+    /// Returns the latitude in degrees.
+    pub const fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    fn fmt_coordinates(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{:?}, {:?}]", self.lng, self.lat)
+    }
+}
+
+/// Geographic polygon, given by an exterior ring and optional interior rings (holes).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoPolygon {
+    rings: Vec<Vec<GeoPoint>>,
+}
+
+impl GeoPolygon {
+    /// Create a new polygon from its exterior ring. The ring must contain at least three
+    /// distinct points; it is closed automatically if the last point differs from the first.
+    pub fn new(exterior: Vec<GeoPoint>) -> Result<Self> {
+        Ok(GeoPolygon {
+            rings: vec![Self::ring(exterior)?],
+        })
+    }
+
+    /// Adds an interior ring, i.e. a hole, to the polygon. The same rules as for the exterior
+    /// ring apply.
+    pub fn with_hole(mut self, hole: Vec<GeoPoint>) -> Result<Self> {
+        self.rings.push(Self::ring(hole)?);
+        Ok(self)
+    }
+
+    /// Returns the exterior ring, followed by the interior rings.
+    pub fn rings(&self) -> &[Vec<GeoPoint>] {
+        &self.rings
+    }
+
+    fn ring(mut points: Vec<GeoPoint>) -> Result<Vec<GeoPoint>> {
+        if points.first() != points.last() {
+            points.push(points[0]);
+        }
+        if points.len() < 4 {
+            bail!(ErrorKind::InvalidArgument(
+                "Polygon ring requires at least three distinct points".to_string()
+            ));
+        }
+        Ok(points)
+    }
+
+    fn fmt_coordinates(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, ring) in self.rings.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "[")?;
+            for (j, point) in ring.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                point.fmt_coordinates(f)?;
+            }
+            write!(f, "]")?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Collection of geographic polygons.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoMultiPolygon {
+    polygons: Vec<GeoPolygon>,
+}
+
+impl GeoMultiPolygon {
+    /// Create a new multi-polygon. Returns an error if no polygons are given.
+    pub fn new(polygons: Vec<GeoPolygon>) -> Result<Self> {
+        if polygons.is_empty() {
+            bail!(ErrorKind::InvalidArgument(
+                "Multi-polygon requires at least one polygon".to_string()
+            ));
+        }
+        Ok(GeoMultiPolygon { polygons })
+    }
+
+    /// Returns the polygons.
+    pub fn polygons(&self) -> &[GeoPolygon] {
+        &self.polygons
+    }
+}
+
+/// Circular region, given by its center and radius in meters. Serializes to the Aerospike
+/// specific `AeroCircle` GeoJSON type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoCircle {
+    center: GeoPoint,
+    radius: f64,
+}
+
+impl GeoCircle {
+    /// Create a new circle. Returns an error if the radius is negative or not finite.
+    pub fn new(center: GeoPoint, radius: f64) -> Result<Self> {
+        if !radius.is_finite() || radius < 0.0 {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Invalid circle radius: {}",
+                radius
+            )));
+        }
+        Ok(GeoCircle { center, radius })
+    }
+
+    /// Returns the center of the circle.
+    pub const fn center(&self) -> GeoPoint {
+        self.center
+    }
+
+    /// Returns the radius of the circle in meters.
+    pub const fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl fmt::Display for GeoPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, r#"{{"type": "Point", "coordinates": "#)?;
+        self.fmt_coordinates(f)?;
+        write!(f, "}}")
+    }
+}
+
+impl fmt::Display for GeoPolygon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, r#"{{"type": "Polygon", "coordinates": "#)?;
+        self.fmt_coordinates(f)?;
+        write!(f, "}}")
+    }
+}
+
+impl fmt::Display for GeoMultiPolygon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, r#"{{"type": "MultiPolygon", "coordinates": ["#)?;
+        for (i, polygon) in self.polygons.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            polygon.fmt_coordinates(f)?;
+        }
+        write!(f, "]}}")
+    }
+}
+
+impl fmt::Display for GeoCircle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, r#"{{"type": "AeroCircle", "coordinates": ["#)?;
+        self.center.fmt_coordinates(f)?;
+        write!(f, ", {:?}]}}", self.radius)
+    }
+}
+
+macro_rules! geo_value {
+    ($($t:ty),*) => {
+        $(
+            impl ToGeoJson for $t {
+                fn to_geojson(&self) -> String {
+                    self.to_string()
+                }
+            }
+
+            impl From<$t> for Value {
+                fn from(val: $t) -> Value {
+                    Value::GeoJSON(val.to_string())
+                }
+            }
+
+            impl<'a> From<&'a $t> for Value {
+                fn from(val: &'a $t) -> Value {
+                    Value::GeoJSON(val.to_string())
+                }
+            }
+        )*
+    };
+}
+
+geo_value!(GeoPoint, GeoPolygon, GeoMultiPolygon, GeoCircle);
+
+#[cfg(test)]
+mod tests {
+    use super::{GeoCircle, GeoMultiPolygon, GeoPoint, GeoPolygon};
+    use crate::Value;
+
+    fn point(lng: f64, lat: f64) -> GeoPoint {
+        GeoPoint::new(lng, lat).unwrap()
+    }
+
+    #[test]
+    fn validation() {
+        assert!(GeoPoint::new(180.5, 0.0).is_err());
+        assert!(GeoPoint::new(0.0, -90.5).is_err());
+        assert!(GeoPoint::new(f64::NAN, 0.0).is_err());
+        assert!(GeoPolygon::new(vec![point(0.0, 0.0), point(1.0, 1.0)]).is_err());
+        assert!(GeoMultiPolygon::new(vec![]).is_err());
+        assert!(GeoCircle::new(point(0.0, 0.0), -1.0).is_err());
+        assert!(GeoCircle::new(point(0.0, 0.0), f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn geojson() {
+        let pt = point(-122.0, 37.5);
+        assert_eq!(
+            pt.to_string(),
+            r#"{"type": "Point", "coordinates": [-122.0, 37.5]}"#
+        );
+        assert_eq!(Value::from(pt), as_geo!(pt.to_string()));
+
+        // rings are closed automatically
+        let polygon =
+            GeoPolygon::new(vec![point(0.0, 0.0), point(1.0, 0.0), point(1.0, 1.0)]).unwrap();
+        assert_eq!(
+            polygon.to_string(),
+            r#"{"type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]}"#
+        );
+
+        let multi = GeoMultiPolygon::new(vec![polygon.clone(), polygon]).unwrap();
+        assert_eq!(
+            multi.to_string(),
+            r#"{"type": "MultiPolygon", "coordinates": [[[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]], [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]]}"#
+        );
+
+        let circle = GeoCircle::new(pt, 1000.0).unwrap();
+        assert_eq!(
+            circle.to_string(),
+            r#"{"type": "AeroCircle", "coordinates": [[-122.0, 37.5], 1000.0]}"#
+        );
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::errors::{ErrorKind, Result};
+
+/// Namespace configuration and statistics of a single node, as returned by
+/// `Client::namespace_info`.
+#[derive(Debug, Clone)]
+pub struct NamespaceInfo {
+    /// Name of the namespace.
+    pub name: String,
+
+    /// Number of records stored on the node, including replicas.
+    pub objects: u64,
+
+    /// Number of tombstones stored on the node, including replicas.
+    pub tombstones: u64,
+
+    /// Effective replication factor of the namespace, if reported by the server.
+    pub replication_factor: Option<u32>,
+
+    /// All configuration values and statistics reported for the namespace.
+    pub stats: HashMap<String, String>,
+}
+
+/// Set statistics, as returned by `Client::sets`.
+#[derive(Debug, Clone)]
+pub struct SetInfo {
+    /// Namespace of the set.
+    pub namespace: String,
+
+    /// Name of the set.
+    pub name: String,
+
+    /// Number of records in the set. Across the cluster, this is the sum of all nodes and
+    /// includes replicas.
+    pub objects: u64,
+
+    /// Number of tombstones in the set. Across the cluster, this is the sum of all nodes and
+    /// includes replicas.
+    pub tombstones: u64,
+
+    /// Memory used by the records of the set, in bytes. Across the cluster, this is the sum of all
+    /// nodes.
+    pub memory_data_bytes: u64,
+
+    /// Last update time of the most recent truncation of the set, in milliseconds since the
+    /// Citrusleaf epoch (2010-01-01 UTC); `0` if the set was never truncated.
+    pub truncate_lut: u64,
+}
+
+impl NamespaceInfo {
+    // Parses the response of the `namespace/<ns>` info command, a list of semicolon separated
+    // `key=value` pairs.
+    pub(crate) fn parse(name: &str, response: &str) -> Result<Self> {
+        let stats = parse_stats(response, ';')?;
+        let replication_factor = match stats
+            .get("effective_replication_factor")
+            .or_else(|| stats.get("replication-factor"))
+        {
+            Some(factor) => Some(factor.parse()?),
+            None => None,
+        };
+
+        Ok(NamespaceInfo {
+            name: name.to_string(),
+            objects: parse_counter(&stats, "objects")?,
+            tombstones: parse_counter(&stats, "tombstones")?,
+            replication_factor,
+            stats: stats
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        })
+    }
+}
+
+impl SetInfo {
+    // Parses the response of the `sets/<ns>` info command. Each set is described by a list of
+    // colon separated `key=value` pairs; sets are separated by semicolons.
+    pub(crate) fn parse_list(response: &str) -> Result<Vec<Self>> {
+        response
+            .split(';')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(Self::parse_entry)
+            .collect()
+    }
+
+    fn parse_entry(entry: &str) -> Result<Self> {
+        let stats = parse_stats(entry, ':')?;
+        let field = |name: &str| {
+            stats.get(name).copied().ok_or_else(|| {
+                ErrorKind::BadResponse(format!("Missing `{}` in set info: {}", name, entry))
+            })
+        };
+
+        // Older servers report the set name as `set_name`; newer servers report the memory used
+        // as `data_used_bytes`.
+        let name = field("set").or_else(|_| field("set_name"))?;
+        let memory_data_bytes = if stats.contains_key("memory_data_bytes") {
+            parse_counter(&stats, "memory_data_bytes")?
+        } else {
+            parse_counter(&stats, "data_used_bytes")?
+        };
+
+        Ok(SetInfo {
+            namespace: field("ns")?.to_string(),
+            name: name.to_string(),
+            objects: parse_counter(&stats, "objects")?,
+            tombstones: parse_counter(&stats, "tombstones")?,
+            memory_data_bytes,
+            truncate_lut: parse_counter(&stats, "truncate_lut")?,
+        })
+    }
+
+    // Adds the statistics of the same set on another node to the cluster-wide statistics.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.objects += other.objects;
+        self.tombstones += other.tombstones;
+        self.memory_data_bytes += other.memory_data_bytes;
+        self.truncate_lut = self.truncate_lut.max(other.truncate_lut);
+    }
+}
+
+// Parses the response of the `bins/<ns>` info command, a comma separated list of bin name
+// statistics, e.g. `bin_names=2`, followed by the bin names.
+pub fn parse_bins(response: &str) -> Vec<String> {
+    response
+        .split(',')
+        .filter(|name| !name.is_empty() && !name.contains('='))
+        .map(ToString::to_string)
+        .collect()
+}
+
+// Parses a list of `key=value` pairs separated by `separator`.
+pub fn parse_stats(response: &str, separator: char) -> Result<HashMap<&str, &str>> {
+    if response.starts_with("ERROR") || response.starts_with("FAIL") {
+        bail!(ErrorKind::BadResponse(format!(
+            "Info command failed: {}",
+            response
+        )));
+    }
+
+    Ok(response
+        .split(separator)
+        .filter_map(|stat| stat.split_once('='))
+        .collect())
+}
+
+fn parse_counter(stats: &HashMap<&str, &str>, name: &str) -> Result<u64> {
+    match stats.get(name) {
+        Some(value) => Ok(value.parse()?),
+        None => Ok(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bins, NamespaceInfo, SetInfo};
+
+    #[test]
+    fn parse_namespace() {
+        let info = NamespaceInfo::parse(
+            "test",
+            "objects=10;tombstones=2;effective_replication_factor=2;storage-engine=memory",
+        )
+        .unwrap();
+        assert_eq!(info.name, "test");
+        assert_eq!(info.objects, 10);
+        assert_eq!(info.tombstones, 2);
+        assert_eq!(info.replication_factor, Some(2));
+        assert_eq!(info.stats.get("storage-engine").unwrap(), "memory");
+
+        assert!(NamespaceInfo::parse("foo", "ERROR::namespace not found").is_err());
+    }
+
+    #[test]
+    fn parse_sets() {
+        let response = "ns=test:set=demo:objects=3:tombstones=1:memory_data_bytes=120:truncate_lut=0;\
+                        ns=test:set=other:objects=5:tombstones=0:data_used_bytes=80:truncate_lut=42;";
+        let mut sets = SetInfo::parse_list(response).unwrap();
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].namespace, "test");
+        assert_eq!(sets[0].name, "demo");
+        assert_eq!(sets[0].objects, 3);
+        assert_eq!(sets[0].tombstones, 1);
+        assert_eq!(sets[0].memory_data_bytes, 120);
+        assert_eq!(sets[1].memory_data_bytes, 80);
+        assert_eq!(sets[1].truncate_lut, 42);
+
+        let other = sets[1].clone();
+        sets[0].merge(&other);
+        assert_eq!(sets[0].objects, 8);
+        assert_eq!(sets[0].memory_data_bytes, 200);
+        assert_eq!(sets[0].truncate_lut, 42);
+
+        assert!(SetInfo::parse_list("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_bin_names() {
+        assert_eq!(
+            parse_bins("bin_names=2,bin_names_quota=32768,a,b"),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert!(parse_bins("bin_names=0,bin_names_quota=32768").is_empty());
+    }
+}
@@ -13,10 +13,11 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::convert::TryFrom;
 use std::fmt;
 use std::result::Result as StdResult;
 
-use crate::errors::Result;
+use crate::errors::{Error, ErrorKind, Result};
 use crate::Value;
 
 use ripemd::digest::Digest;
@@ -45,14 +46,23 @@ pub struct Key {
 impl Key {
     /// Construct a new key given a namespace, a set name and a user key value.
     ///
+    /// Unsigned 64-bit integer keys are stored as signed 64-bit integers with the same two's
+    /// complement bit pattern, i.e. `u64::MAX` and `-1` identify the same record. See `KeyValue`
+    /// for details.
+    ///
     /// # Panics
     ///
     /// Only integers, strings and blobs (`Vec<u8>`) can be used as user keys. The constructor will
-    /// panic if any other value type is passed.
+    /// panic if any other value type is passed. Use `Key::with_user_key` to have invalid key types
+    /// rejected at compile time instead.
     pub fn new<S>(namespace: S, set_name: S, key: Value) -> Result<Self>
     where
         S: Into<String>,
     {
+        let key = match key {
+            Value::UInt(val) => Value::Int(val as i64),
+            key => key,
+        };
         let mut key = Key {
             namespace: namespace.into(),
             set_name: set_name.into(),
@@ -64,6 +74,33 @@ impl Key {
         Ok(key)
     }
 
+    /// Construct a new key given a namespace, a set name and a user key. Unlike `Key::new`, this
+    /// constructor only accepts value types that are valid user keys and cannot fail.
+    pub fn with_user_key<S, K>(namespace: S, set_name: S, key: K) -> Self
+    where
+        S: Into<String>,
+        K: Into<KeyValue>,
+    {
+        let mut key = Key {
+            namespace: namespace.into(),
+            set_name: set_name.into(),
+            digest: [0; 20],
+            user_key: Some(key.into().into()),
+        };
+
+        key.compute_digest()
+            .expect("Key values can always be hashed");
+        key
+    }
+
+    /// Returns the user key as a `KeyValue`, if the key contains the user key and it is of a
+    /// valid key type.
+    pub fn user_key_value(&self) -> Option<KeyValue> {
+        self.user_key
+            .as_ref()
+            .and_then(|key| KeyValue::try_from(key.clone()).ok())
+    }
+
     fn compute_digest(&mut self) -> Result<()> {
         let mut hash = Ripemd160::new();
         hash.update(self.set_name.as_bytes());
@@ -96,6 +133,175 @@ impl fmt::Display for Key {
     }
 }
 
+/// User key value. Only integers, strings and blobs can be used as user keys; the server does not
+/// support floating point or collection keys, and the corresponding value types do not convert
+/// into a `KeyValue`.
+///
+/// The server stores integer keys as signed 64-bit integers. Unsigned 64-bit integers are
+/// therefore encoded using the same two's complement bit pattern: values up to `i64::MAX` are
+/// stored unchanged, larger values are stored as negative integers, e.g. `u64::MAX` as `-1`.
+/// Keys returned by the server, e.g. in `Record::key`, contain the signed value;
+/// `KeyValue::as_u64` reverses the encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// # use aerospike::{Key, KeyValue};
+///
+/// let key = Key::with_user_key("test", "ids", u64::MAX);
+/// assert_eq!(key.user_key_value(), Some(KeyValue::Int(-1)));
+/// assert_eq!(key.user_key_value().unwrap().as_u64(), Some(u64::MAX));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KeyValue {
+    /// Integer key.
+    Int(i64),
+
+    /// String key.
+    String(String),
+
+    /// Blob key.
+    Blob(Vec<u8>),
+}
+
+impl KeyValue {
+    /// Returns the integer key as an unsigned 64-bit integer, reversing the two's complement
+    /// encoding of unsigned keys. Returns `None` for string and blob keys.
+    pub const fn as_u64(&self) -> Option<u64> {
+        match *self {
+            KeyValue::Int(val) => Some(val as u64),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer key. Returns `None` for string and blob keys.
+    pub const fn as_i64(&self) -> Option<i64> {
+        match *self {
+            KeyValue::Int(val) => Some(val),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KeyValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> StdResult<(), fmt::Error> {
+        Value::from(self.clone()).fmt(f)
+    }
+}
+
+impl From<KeyValue> for Value {
+    fn from(val: KeyValue) -> Value {
+        match val {
+            KeyValue::Int(val) => Value::Int(val),
+            KeyValue::String(val) => Value::String(val),
+            KeyValue::Blob(val) => Value::Blob(val),
+        }
+    }
+}
+
+impl TryFrom<Value> for KeyValue {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<KeyValue> {
+        match val {
+            Value::Int(val) => Ok(KeyValue::Int(val)),
+            Value::UInt(val) => Ok(KeyValue::Int(val as i64)),
+            Value::String(val) => Ok(KeyValue::String(val)),
+            Value::Blob(val) => Ok(KeyValue::Blob(val)),
+            val => bail!(ErrorKind::InvalidArgument(format!(
+                "Data type is not supported as Key value: {}",
+                val
+            ))),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for KeyValue {
+                fn from(val: $t) -> KeyValue {
+                    KeyValue::Int(i64::from(val))
+                }
+            }
+
+            impl<'a> From<&'a $t> for KeyValue {
+                fn from(val: &'a $t) -> KeyValue {
+                    KeyValue::Int(i64::from(*val))
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, u8, i16, u16, i32, u32, i64);
+
+impl From<u64> for KeyValue {
+    fn from(val: u64) -> KeyValue {
+        KeyValue::Int(val as i64)
+    }
+}
+
+impl<'a> From<&'a u64> for KeyValue {
+    fn from(val: &'a u64) -> KeyValue {
+        KeyValue::Int(*val as i64)
+    }
+}
+
+impl From<isize> for KeyValue {
+    fn from(val: isize) -> KeyValue {
+        KeyValue::Int(val as i64)
+    }
+}
+
+impl<'a> From<&'a isize> for KeyValue {
+    fn from(val: &'a isize) -> KeyValue {
+        KeyValue::Int(*val as i64)
+    }
+}
+
+impl From<usize> for KeyValue {
+    fn from(val: usize) -> KeyValue {
+        KeyValue::Int(val as i64)
+    }
+}
+
+impl<'a> From<&'a usize> for KeyValue {
+    fn from(val: &'a usize) -> KeyValue {
+        KeyValue::Int(*val as i64)
+    }
+}
+
+impl From<String> for KeyValue {
+    fn from(val: String) -> KeyValue {
+        KeyValue::String(val)
+    }
+}
+
+impl<'a> From<&'a String> for KeyValue {
+    fn from(val: &'a String) -> KeyValue {
+        KeyValue::String(val.clone())
+    }
+}
+
+impl<'a> From<&'a str> for KeyValue {
+    fn from(val: &'a str) -> KeyValue {
+        KeyValue::String(val.to_string())
+    }
+}
+
+impl From<Vec<u8>> for KeyValue {
+    fn from(val: Vec<u8>) -> KeyValue {
+        KeyValue::Blob(val)
+    }
+}
+
+impl<'a> From<&'a [u8]> for KeyValue {
+    fn from(val: &'a [u8]) -> KeyValue {
+        KeyValue::Blob(val.to_vec())
+    }
+}
+
 /// Construct a new key given a namespace, a set name and a user key.
 ///
 /// # Panics
@@ -111,8 +317,12 @@ macro_rules! as_key {
 
 #[cfg(test)]
 mod tests {
+    use std::convert::TryFrom;
     use std::str;
 
+    use super::{Key, KeyValue};
+    use crate::Value;
+
     macro_rules! digest {
         ($x:expr) => {
             hex::encode(as_key!("namespace", "set", $x).digest)
@@ -272,8 +482,31 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Aerospike does not support u64 natively on server-side.")]
-    fn unsupported_u64_key() {
-        as_key!("namespace", "set", u64::max_value());
+    fn u64_keys() {
+        // unsigned keys use the two's complement encoding of signed keys
+        assert_eq!(digest!(1u64), digest!(1i64));
+        assert_eq!(digest!(u64::max_value()), digest!(-1i64));
+        assert_eq!(digest!(1u64 << 63), digest!(i64::min_value()));
+
+        let key = Key::with_user_key("namespace", "set", u64::max_value());
+        assert_eq!(key.digest, as_key!("namespace", "set", -1).digest);
+        assert_eq!(key.user_key, Some(Value::Int(-1)));
+        assert_eq!(
+            key.user_key_value().unwrap().as_u64(),
+            Some(u64::max_value())
+        );
+    }
+
+    #[test]
+    fn key_values() {
+        let key = Key::with_user_key("namespace", "set", "haha");
+        assert_eq!(key, as_key!("namespace", "set", "haha"));
+        let key = Key::with_user_key("namespace", "set", vec![b'a'; 10]);
+        assert_eq!(key, as_key!("namespace", "set", vec![b'a'; 10]));
+        let key = Key::with_user_key("namespace", "set", 42u32);
+        assert_eq!(key.user_key_value(), Some(KeyValue::Int(42)));
+
+        assert!(KeyValue::try_from(Value::from(4.1415)).is_err());
+        assert_eq!(KeyValue::from("a").as_u64(), None);
     }
 }
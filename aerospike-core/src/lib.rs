@@ -154,21 +154,27 @@ pub use cluster::Node;
 pub use commands::particle_type::ParticleType;
 pub use errors::{Error, ErrorKind, Result};
 pub use expressions::regex_flag::RegexFlag;
-pub use key::Key;
+pub use geo::{GeoCircle, GeoMultiPolygon, GeoPoint, GeoPolygon, ToGeoJson};
+pub use info::{NamespaceInfo, SetInfo};
+pub use key::{Key, KeyValue};
 pub use net::Host;
 pub use net::ToHosts;
 pub use operations::{MapPolicy, MapReturnType, MapWriteMode};
 pub use policy::{
     BatchPolicy, ClientPolicy, CommitLevel, Concurrency, ConsistencyLevel, Expiration,
     GenerationPolicy, Policy, Priority, QueryPolicy, ReadPolicy, RecordExistsAction, ScanPolicy,
-    WritePolicy,
+    UpdatePolicy, WritePolicy,
 };
-pub use query::{CollectionIndexType, IndexType, Recordset, Statement, UDFLang};
-pub use record::Record;
+pub use query::{
+    Aggregator, CollectionIndexType, IndexInfo, IndexType, Recordset, Statement, UDFInfo, UDFLang,
+};
+pub use record::{Record, RecordRef};
 pub use result_code::ResultCode;
 pub use task::{IndexTask, RegisterTask, Task};
+pub use txn::{AbortStatus, CommitError, CommitStatus, Txn, TxnState};
+pub use update::Update;
 pub use user::User;
-pub use value::{FloatValue, Value};
+pub use value::{FloatValue, Value, ValueRef};
 pub use commands::operate_command::OperateRecord;
 
 #[macro_use]
@@ -184,6 +190,8 @@ mod client;
 mod cluster;
 mod commands;
 pub mod expressions;
+mod geo;
+mod info;
 mod msgpack;
 mod net;
 pub mod operations;
@@ -192,6 +200,8 @@ pub mod query;
 mod record;
 mod result_code;
 pub mod task;
+mod txn;
+mod update;
 mod user;
 
 #[cfg(test)]
@@ -21,6 +21,13 @@ use crate::commands::ParticleType;
 use crate::errors::{ErrorKind, Result};
 use crate::value::Value;
 
+// Map extension flag set on K-ordered and KV-ordered maps.
+const MAP_FLAG_KEY_ORDERED: u8 = 0x01;
+
+// Map extension flag set on map operation results whose entries are returned in range order, e.g.
+// key/value results of rank range getters.
+const MAP_FLAG_PRESERVE_ORDER: u8 = 0x08;
+
 pub fn unpack_value_list(buf: &mut Buffer) -> Result<Value> {
     if buf.data_buffer.is_empty() {
         return Ok(Value::List(vec![]));
@@ -71,12 +78,25 @@ fn unpack_list(buf: &mut Buffer, mut count: usize) -> Result<Value> {
 }
 
 fn unpack_map(buf: &mut Buffer, mut count: usize) -> Result<Value> {
+    let mut ordered = false;
     if count > 0 && is_ext(buf.peek()) {
-        let _uv = unpack_value(buf);
+        let flags = unpack_ext_type(buf);
+        ordered = flags & (MAP_FLAG_KEY_ORDERED | MAP_FLAG_PRESERVE_ORDER) != 0;
         let _uv = unpack_value(buf);
         count -= 1;
     }
 
+    // Ordered maps keep the order the server sent the entries in.
+    if ordered {
+        let mut map: Vec<(Value, Value)> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let key = unpack_value(buf)?;
+            let val = unpack_value(buf)?;
+            map.push((key, val));
+        }
+        return Ok(Value::OrderedMap(map));
+    }
+
     let mut map: HashMap<Value, Value> = HashMap::with_capacity(count);
     for _ in 0..count {
         let key = unpack_value(buf)?;
@@ -135,18 +155,18 @@ fn unpack_value(buf: &mut Buffer) -> Result<Value> {
             Ok(unpack_blob(buf, count as usize)?)
         }
         0xc7 => {
-            let count = 1 + buf.read_u8(None);
-            buf.skip_bytes(count as usize);
+            let count = 1 + buf.read_u8(None) as usize;
+            buf.skip_bytes(count);
             Ok(Value::Nil)
         }
         0xc8 => {
-            let count = 1 + buf.read_u16(None);
-            buf.skip_bytes(count as usize);
+            let count = 1 + buf.read_u16(None) as usize;
+            buf.skip_bytes(count);
             Ok(Value::Nil)
         }
         0xc9 => {
-            let count = 1 + buf.read_u32(None);
-            buf.skip_bytes(count as usize);
+            let count = 1 + buf.read_u32(None) as usize;
+            buf.skip_bytes(count);
             Ok(Value::Nil)
         }
         0xca => Ok(Value::from(buf.read_f32(None))),
@@ -211,6 +231,25 @@ fn unpack_value(buf: &mut Buffer) -> Result<Value> {
     }
 }
 
+// Reads a msgpack extension and returns its type. The server stores the map flags in the type of
+// the extension that leads a map.
+fn unpack_ext_type(buf: &mut Buffer) -> u8 {
+    let obj_type = buf.read_u8(None);
+    let count = match obj_type {
+        0xc7 => buf.read_u8(None) as usize,
+        0xc8 => buf.read_u16(None) as usize,
+        0xc9 => buf.read_u32(None) as usize,
+        0xd4 => 1,
+        0xd5 => 2,
+        0xd6 => 4,
+        0xd7 => 8,
+        _ => 16,
+    };
+    let ext_type = buf.read_u8(None);
+    buf.skip_bytes(count);
+    ext_type
+}
+
 const fn is_ext(byte: u8) -> bool {
     matches!(byte, 0xc7 | 0xc8 | 0xc9 | 0xd4 | 0xd5 | 0xd6 | 0xd7 | 0xd8)
 }
@@ -38,7 +38,7 @@ pub fn pack_value(buf: &mut Option<&mut Buffer>, val: &Value) -> usize {
         Value::Blob(ref val) | Value::HLL(ref val) => pack_blob(buf, val),
         Value::List(ref val) => pack_array(buf, val),
         Value::HashMap(ref val) => pack_map(buf, val),
-        Value::OrderedMap(_) => panic!("Ordered maps are not supported in this encoder."),
+        Value::OrderedMap(ref val) => pack_ordered_map(buf, val),
         Value::GeoJSON(ref val) => pack_geo_json(buf, val),
     }
 }
@@ -81,18 +81,7 @@ pub fn pack_cdt_op(
         size += pack_integer(buf, i64::from(cdt_op.op));
     }
 
-    if !cdt_op.args.is_empty() {
-        for arg in &cdt_op.args {
-            size += match *arg {
-                CdtArgument::Byte(byte) => pack_value(buf, &Value::from(byte)),
-                CdtArgument::Int(int) => pack_value(buf, &Value::from(int)),
-                CdtArgument::Value(value) => pack_value(buf, value),
-                CdtArgument::List(list) => pack_array(buf, list),
-                CdtArgument::Map(map) => pack_map(buf, map),
-                CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val)),
-            }
-        }
-    }
+    size += pack_cdt_args(buf, &cdt_op.args);
 
     size
 }
@@ -106,18 +95,7 @@ pub fn pack_hll_op(
     let mut size: usize = 0;
     size += pack_array_begin(buf, hll_op.args.len() + 1);
     size += pack_integer(buf, i64::from(hll_op.op));
-    if !hll_op.args.is_empty() {
-        for arg in &hll_op.args {
-            size += match *arg {
-                CdtArgument::Byte(byte) => pack_value(buf, &Value::from(byte)),
-                CdtArgument::Int(int) => pack_value(buf, &Value::from(int)),
-                CdtArgument::Value(value) => pack_value(buf, value),
-                CdtArgument::List(list) => pack_array(buf, list),
-                CdtArgument::Map(map) => pack_map(buf, map),
-                CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val)),
-            }
-        }
-    }
+    size += pack_cdt_args(buf, &hll_op.args);
     size
 }
 
@@ -146,17 +124,69 @@ pub fn pack_cdt_bit_op(
     size += pack_array_begin(buf, cdt_op.args.len() + 1);
     size += pack_integer(buf, i64::from(cdt_op.op));
 
-    if !cdt_op.args.is_empty() {
-        for arg in &cdt_op.args {
-            size += match *arg {
-                CdtArgument::Byte(byte) => pack_value(buf, &Value::from(byte)),
-                CdtArgument::Int(int) => pack_value(buf, &Value::from(int)),
-                CdtArgument::Value(value) => pack_value(buf, value),
-                CdtArgument::List(list) => pack_array(buf, list),
-                CdtArgument::Map(map) => pack_map(buf, map),
-                CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val)),
-            }
+    size += pack_cdt_args(buf, &cdt_op.args);
+    size
+}
+
+#[doc(hidden)]
+pub fn pack_cdt_create_op(
+    buf: &mut Option<&mut Buffer>,
+    cdt_op: &CdtOperation,
+    ctx: &[CdtContext],
+    flag: u8,
+) -> usize {
+    if ctx.is_empty() {
+        return pack_cdt_op(buf, cdt_op, ctx);
+    }
+
+    let mut size: usize = 0;
+    size += pack_array_begin(buf, 3);
+    size += pack_integer(buf, 0xff);
+    size += pack_array_begin(buf, ctx.len() * 2);
+
+    // The create flag is applied to the innermost context, where the container is created.
+    let last = ctx.len() - 1;
+    for (i, c) in ctx.iter().enumerate() {
+        let flags = if i == last { c.flags | flag } else { c.flags };
+        size += pack_integer(buf, i64::from(c.id | flags));
+        size += pack_value(buf, &c.value);
+    }
+
+    size += pack_array_begin(buf, cdt_op.args.len() + 1);
+    size += pack_integer(buf, i64::from(cdt_op.op));
+
+    size += pack_cdt_args(buf, &cdt_op.args);
+    size
+}
+
+#[doc(hidden)]
+pub fn pack_cdt_args(buf: &mut Option<&mut Buffer>, args: &[CdtArgument]) -> usize {
+    let mut size: usize = 0;
+    for arg in args {
+        size += match *arg {
+            CdtArgument::Byte(byte) => pack_value(buf, &Value::from(byte)),
+            CdtArgument::Int(int) => pack_value(buf, &Value::from(int)),
+            CdtArgument::Value(ref value) => pack_value(buf, value),
+            CdtArgument::List(ref list) => pack_array(buf, list),
+            CdtArgument::Map(ref map) => pack_map(buf, map),
+            CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val)),
+        }
+    }
+    size
+}
+
+#[doc(hidden)]
+pub fn pack_cdt_context(buf: &mut Option<&mut Buffer>, ctx: &[CdtContext]) -> usize {
+    let mut size: usize = 0;
+    size += pack_array_begin(buf, ctx.len() * 2);
+
+    for c in ctx {
+        if c.id == 0 {
+            size += pack_integer(buf, i64::from(c.id));
+        } else {
+            size += pack_integer(buf, i64::from(c.id | c.flags));
         }
+        size += pack_value(buf, &c.value);
     }
     size
 }
@@ -186,6 +216,20 @@ pub fn pack_map(buf: &mut Option<&mut Buffer>, map: &HashMap<Value, Value>) -> u
     size
 }
 
+// Packs the entries in the given order. The server determines the order of the stored map from
+// the map policy, not from the order of the entries.
+fn pack_ordered_map(buf: &mut Option<&mut Buffer>, map: &[(Value, Value)]) -> usize {
+    let mut size = 0;
+
+    size += pack_map_begin(buf, map.len());
+    for (key, val) in map {
+        size += pack_value(buf, key);
+        size += pack_value(buf, val);
+    }
+
+    size
+}
+
 /// ///////////////////////////////////////////////////////////////////
 
 const MSGPACK_MARKER_NIL: u8 = 0xc0;
@@ -42,17 +42,29 @@ pub struct Connection {
 }
 
 impl Connection {
+    // Opens a connection and authenticates it. The connect timeout covers both steps.
     pub async fn new(addr: &str, policy: &ClientPolicy) -> Result<Self> {
-        let stream = aerospike_rt::timeout(Duration::from_secs(10), TcpStream::connect(addr)).await;
-        if stream.is_err() {
+        let Some(timeout) = policy.connect_timeout else {
+            return Self::connect(addr, policy).await;
+        };
+        match aerospike_rt::timeout(timeout, Self::connect(addr, policy)).await {
+            Ok(res) => res,
+            Err(_) => bail!(ErrorKind::Connection(
+                "Could not open network connection".to_string()
+            )),
+        }
+    }
+
+    async fn connect(addr: &str, policy: &ClientPolicy) -> Result<Self> {
+        let Ok(stream) = TcpStream::connect(addr).await else {
             bail!(ErrorKind::Connection(
                 "Could not open network connection".to_string()
             ));
-        }
+        };
         let mut conn = Connection {
             buffer: Buffer::new(policy.buffer_reclaim_threshold),
             bytes_read: 0,
-            conn: stream.unwrap()?,
+            conn: stream,
             idle_timeout: policy.idle_timeout,
             idle_deadline: policy.idle_timeout.map(|timeout| Instant::now() + timeout),
         };
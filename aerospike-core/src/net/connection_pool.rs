@@ -17,12 +17,12 @@ use std::ops::{Deref, DerefMut, Drop};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
-use crate::errors::{Error, ErrorKind, Result};
+use crate::errors::{ErrorKind, Result};
 use crate::net::{Connection, Host};
 use crate::policy::ClientPolicy;
 use std::sync::Mutex;
 use std::collections::VecDeque;
-use std::time::Duration;
+use aerospike_rt::time::{Duration, Instant};
 use aerospike_rt::{Semaphore, OwnedSemaphorePermit};
 
 #[derive(Debug)]
@@ -32,6 +32,8 @@ struct IdleConnection(Connection);
 struct SharedQueue {
     // SYNCHRONOUS LOCK! Do not hold across an await point or it _will_ deadlock.
     connections: Mutex<VecDeque<IdleConnection>>,
+    capacity: usize,
+    min_size: usize,
     host: Host,
     policy: ClientPolicy,
 }
@@ -40,21 +42,38 @@ struct SharedQueue {
 struct Queue(Arc<SharedQueue>, Arc<Semaphore>);
 
 impl Queue {
-    pub fn with_capacity(capacity: usize, host: Host, policy: ClientPolicy) -> Self {
+    pub fn with_capacity(
+        capacity: usize,
+        min_size: usize,
+        host: Host,
+        policy: ClientPolicy,
+    ) -> Self {
         let shared = SharedQueue {
             connections: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+            min_size,
             host,
             policy,
         };
         Queue(Arc::new(shared), Arc::new(Semaphore::new(capacity)))
     }
 
-    pub async fn get(&self) -> Result<PooledConnection> {
-        let Ok(permit) = self.1.clone().try_acquire_owned() else {
-            bail!(ErrorKind::Connection(
-                "Too many connections".to_string()
-            ));
-        };
+    // Number of connections currently owned by this queue, both idle and checked out.
+    fn total_connections(&self) -> usize {
+        let in_use = self.0.capacity - self.1.available_permits();
+        let idle = self.0.connections.lock().unwrap().len();
+        in_use + idle
+    }
+
+    async fn new_connection(&self) -> Result<Connection> {
+        Connection::new(&self.0.host.address(), &self.0.policy).await
+    }
+
+    pub async fn get(
+        &self,
+        permit: OwnedSemaphorePermit,
+        node_permit: OwnedSemaphorePermit,
+    ) -> Result<PooledConnection> {
         let mut connections_to_free = Vec::new();
         let mut conn: Option<Connection> = None;
         {
@@ -74,25 +93,15 @@ impl Queue {
         }
 
         if conn.is_none() {
-            let new_conn = aerospike_rt::timeout(
-                Duration::from_secs(5),
-                Connection::new(&self.0.host.address(), &self.0.policy),
-            )
-            .await;
-
-            let Ok(Ok(new_conn)) = new_conn else {
-                bail!(ErrorKind::Connection(
-                    "Could not open network connection".to_string()
-                ));
-            };
-
-            conn = Some(new_conn);
+            conn = Some(self.new_connection().await?);
         }
 
         Ok(PooledConnection {
             queue: self.clone(),
             conn,
+            busy: false,
             _permit: permit,
+            _node_permit: node_permit,
         })
     }
 
@@ -105,6 +114,53 @@ impl Queue {
         aerospike_rt::spawn(async move { conn.close().await });
     }
 
+    // Opens new connections until the queue holds at least `min_size` connections. Returns the
+    // number of connections opened.
+    pub async fn fill(&self, node_permits: &Arc<Semaphore>) -> Result<usize> {
+        let mut opened = 0;
+        while self.total_connections() < self.0.min_size {
+            // Hold permits while connecting so that neither the queue nor the node exceed their
+            // capacity.
+            let Ok(_node_permit) = node_permits.clone().try_acquire_owned() else {
+                break;
+            };
+            let Ok(_permit) = self.1.clone().try_acquire_owned() else {
+                break;
+            };
+            let conn = self.new_connection().await?;
+            self.put_back(conn);
+            opened += 1;
+        }
+        Ok(opened)
+    }
+
+    // Closes idle connections while the queue holds more than `min_size` connections. Returns
+    // the number of connections closed.
+    pub async fn reap_idle(&self) -> usize {
+        let idle_connections = {
+            let mut connections = self.0.connections.lock().unwrap();
+            let in_use = self.0.capacity - self.1.available_permits();
+            let mut excess = (in_use + connections.len()).saturating_sub(self.0.min_size);
+            let mut idle_connections = Vec::new();
+            let mut kept = VecDeque::with_capacity(connections.len());
+            for IdleConnection(conn) in connections.drain(..) {
+                if excess > 0 && conn.is_idle() {
+                    excess -= 1;
+                    idle_connections.push(conn);
+                } else {
+                    kept.push_back(IdleConnection(conn));
+                }
+            }
+            *connections = kept;
+            idle_connections
+        };
+        let closed = idle_connections.len();
+        for mut conn in idle_connections {
+            conn.close().await;
+        }
+        closed
+    }
+
     pub async fn clear(&mut self) {
         let connections = {
             let mut connections = self.0.connections.lock().unwrap();
@@ -122,28 +178,41 @@ pub struct ConnectionPool {
     num_queues: usize,
     queues: Vec<Queue>,
     queue_counter: AtomicUsize,
+    // Node-wide connection permits. Every checked out connection holds one of these in addition
+    // to the permit of its queue, so that commands waiting for a connection are served in FIFO
+    // order regardless of which queue frees up a connection first.
+    permits: Arc<Semaphore>,
+    wait_timeout: Option<Duration>,
 }
 
 impl ConnectionPool {
     pub fn new(host: Host, policy: ClientPolicy) -> Self {
         let num_conns = policy.max_conns_per_node;
+        let min_conns = policy.min_conns_per_node.min(num_conns);
         let num_queues = policy.conn_pools_per_node;
-        let queues = ConnectionPool::initialize_queues(num_conns, num_queues, host, policy);
+        let wait_timeout = policy.conn_wait_timeout;
+        let queues =
+            ConnectionPool::initialize_queues(num_conns, min_conns, num_queues, host, policy);
         ConnectionPool {
             num_queues,
             queues,
             queue_counter: AtomicUsize::default(),
+            permits: Arc::new(Semaphore::new(num_conns)),
+            wait_timeout,
         }
     }
 
     fn initialize_queues(
         num_conns: usize,
+        min_conns: usize,
         num_queues: usize,
         host: Host,
         policy: ClientPolicy,
     ) -> Vec<Queue> {
         let max = num_conns / num_queues;
         let mut rem = num_conns % num_queues;
+        let min = min_conns / num_queues;
+        let mut min_rem = min_conns % num_queues;
         let mut queues = Vec::with_capacity(num_queues);
         for _ in 0..num_queues {
             let mut capacity = max;
@@ -151,31 +220,96 @@ impl ConnectionPool {
                 capacity += 1;
                 rem -= 1;
             }
-            queues.push(Queue::with_capacity(capacity, host.clone(), policy.clone()));
+            let mut min_size = min;
+            if min_rem > 0 {
+                min_size += 1;
+                min_rem -= 1;
+            }
+            queues.push(Queue::with_capacity(
+                capacity,
+                min_size.min(capacity),
+                host.clone(),
+                policy.clone(),
+            ));
         }
         queues
     }
 
-    pub async fn get(&self) -> Result<PooledConnection> {
-        if self.num_queues == 1 {
-            self.queues[0].get().await
-        } else {
-            let mut attempts = self.num_queues;
-            loop {
-                let i = self.queue_counter.fetch_add(1, Ordering::Relaxed);
-                let connection = self.queues[i % self.num_queues].get().await;
-                if let Err(Error(ErrorKind::NoMoreConnections, _)) = connection {
-                    attempts -= 1;
-                    if attempts > 0 {
-                        continue;
-                    }
+    pub async fn get(&self, deadline: Option<Instant>) -> Result<PooledConnection> {
+        let node_permit = self.acquire_permit(deadline).await?;
+
+        // Holding a node permit guarantees that at least one queue has a free slot.
+        for _ in 0..self.num_queues {
+            let i = self.queue_counter.fetch_add(1, Ordering::Relaxed);
+            let queue = &self.queues[i % self.num_queues];
+            if let Ok(permit) = queue.1.clone().try_acquire_owned() {
+                return queue.get(permit, node_permit).await;
+            }
+        }
+
+        bail!(ErrorKind::NoMoreConnections)
+    }
+
+    async fn acquire_permit(&self, deadline: Option<Instant>) -> Result<OwnedSemaphorePermit> {
+        if let Ok(permit) = self.permits.clone().try_acquire_owned() {
+            return Ok(permit);
+        }
+
+        let Some(wait_timeout) = self.wait_timeout else {
+            bail!(ErrorKind::NoMoreConnections);
+        };
+
+        let wait_deadline = Instant::now() + wait_timeout;
+        let wait_deadline = deadline.map_or(wait_deadline, |deadline| deadline.min(wait_deadline));
+        match aerospike_rt::timeout_at(wait_deadline, self.permits.clone().acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(permit),
+            Ok(Err(_)) => bail!(ErrorKind::Connection("Connection pool closed".to_string())),
+            Err(_) => bail!(ErrorKind::ConnectionWaitTimeout(format!(
+                "all {} connections in use",
+                self.queues
+                    .iter()
+                    .map(|queue| queue.0.capacity)
+                    .sum::<usize>()
+            ))),
+        }
+    }
+
+    // Opens connections until every queue holds its share of `min_conns_per_node`.
+    pub async fn fill(&self) -> Result<usize> {
+        // A queue that fails to connect does not keep the other queues from being filled. The
+        // first error is returned once all queues were tried.
+        let mut opened = 0;
+        let mut first_err = None;
+        for queue in &self.queues {
+            match queue.fill(&self.permits).await {
+                Ok(count) => opened += count,
+                Err(err) => {
+                    first_err.get_or_insert(err);
                 }
-                return connection;
             }
         }
+        first_err.map_or(Ok(opened), Err)
+    }
+
+    // Closes idle connections beyond the minimum pool size, then tops the pool back up to the
+    // minimum. Called periodically by the cluster tend.
+    pub async fn balance(&self) -> Result<()> {
+        let mut closed = 0;
+        for queue in &self.queues {
+            closed += queue.reap_idle().await;
+        }
+        let opened = self.fill().await?;
+        if closed > 0 || opened > 0 {
+            debug!(
+                "Connection pool balanced: {} idle closed, {} opened",
+                closed, opened
+            );
+        }
+        Ok(())
     }
 
     pub async fn close(&mut self) {
+        self.permits.close();
         for mut queue in self.queues.drain(..) {
             queue.clear().await;
         }
@@ -186,7 +320,9 @@ impl ConnectionPool {
 pub struct PooledConnection {
     queue: Queue,
     pub conn: Option<Connection>,
+    busy: bool,
     _permit: OwnedSemaphorePermit,
+    _node_permit: OwnedSemaphorePermit,
 }
 
 impl PooledConnection {
@@ -194,12 +330,23 @@ impl PooledConnection {
         let conn = self.conn.take().unwrap();
         self.queue.drop_conn(conn);
     }
+
+    // Marks the connection as being in the middle of a request/response exchange. A busy
+    // connection that gets dropped, e.g. because the command future was cancelled, is closed
+    // instead of being returned to the pool with unread data on the socket.
+    pub fn set_busy(&mut self, busy: bool) {
+        self.busy = busy;
+    }
 }
 
 impl Drop for PooledConnection {
     fn drop(&mut self) {
         if let Some(conn) = self.conn.take() {
-            self.queue.put_back(conn);
+            if self.busy {
+                self.queue.drop_conn(conn);
+            } else {
+                self.queue.put_back(conn);
+            }
         }
     }
 }
@@ -217,3 +364,50 @@ impl DerefMut for PooledConnection {
         self.conn.as_mut().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ConnectionPool;
+    use crate::errors::{Error, ErrorKind};
+    use crate::net::Host;
+    use crate::policy::ClientPolicy;
+    use aerospike_rt::time::Duration;
+
+    #[test]
+    fn initialize_queues() {
+        let host = Host::new("localhost", 3000);
+        let queues = ConnectionPool::initialize_queues(10, 5, 3, host, ClientPolicy::default());
+        let capacities: Vec<usize> = queues.iter().map(|q| q.0.capacity).collect();
+        let min_sizes: Vec<usize> = queues.iter().map(|q| q.0.min_size).collect();
+        assert_eq!(capacities, vec![4, 3, 3]);
+        assert_eq!(min_sizes, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn acquire_permit() {
+        let rt = aerospike_rt::tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut policy = ClientPolicy::default();
+            policy.max_conns_per_node = 1;
+            let pool = ConnectionPool::new(Host::new("localhost", 3000), policy.clone());
+            let _permit = pool.acquire_permit(None).await.unwrap();
+            assert!(matches!(
+                pool.acquire_permit(None).await,
+                Err(Error(ErrorKind::NoMoreConnections, _))
+            ));
+
+            policy.conn_wait_timeout = Some(Duration::from_millis(10));
+            let pool = ConnectionPool::new(Host::new("localhost", 3000), policy);
+            let permit = pool.acquire_permit(None).await.unwrap();
+            assert!(matches!(
+                pool.acquire_permit(None).await,
+                Err(Error(ErrorKind::ConnectionWaitTimeout(_), _))
+            ));
+            drop(permit);
+            assert!(pool.acquire_permit(None).await.is_ok());
+        });
+    }
+}
@@ -27,6 +27,8 @@
 //! // bin result = [[0b00000001, 0b01000010, 0b00000000], [0b01011010]]
 //! ```
 
+use std::borrow::Cow;
+
 use crate::msgpack::encoder::pack_cdt_bit_op;
 use crate::operations::cdt::{CdtArgument, CdtOperation};
 use crate::operations::cdt_context::DEFAULT_CTX;
@@ -153,8 +155,8 @@ pub fn resize<'a>(
     };
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -181,15 +183,15 @@ pub fn insert<'a>(
         encoder: Box::new(pack_cdt_bit_op),
         args: vec![
             CdtArgument::Int(byte_offset),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -223,8 +225,8 @@ pub fn remove<'a>(
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -254,15 +256,15 @@ pub fn set<'a>(
         args: vec![
             CdtArgument::Int(bit_offset),
             CdtArgument::Int(bit_size),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -292,15 +294,15 @@ pub fn or<'a>(
         args: vec![
             CdtArgument::Int(bit_offset),
             CdtArgument::Int(bit_size),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -330,15 +332,15 @@ pub fn xor<'a>(
         args: vec![
             CdtArgument::Int(bit_offset),
             CdtArgument::Int(bit_size),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -368,15 +370,15 @@ pub fn and<'a>(
         args: vec![
             CdtArgument::Int(bit_offset),
             CdtArgument::Int(bit_size),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -410,8 +412,8 @@ pub fn not<'a>(
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -448,8 +450,8 @@ pub fn lshift<'a>(
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -486,8 +488,8 @@ pub fn rshift<'a>(
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -535,8 +537,8 @@ pub fn add<'a>(
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -584,8 +586,8 @@ pub fn subtract<'a>(
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -622,8 +624,8 @@ pub fn set_int<'a>(
 
     Operation {
         op: OperationType::BitWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -647,8 +649,8 @@ pub fn get(bin: &str, bit_offset: i64, bit_size: i64) -> Operation {
 
     Operation {
         op: OperationType::BitRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -672,8 +674,8 @@ pub fn count(bin: &str, bit_offset: i64, bit_size: i64) -> Operation {
 
     Operation {
         op: OperationType::BitRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -703,8 +705,8 @@ pub fn lscan(bin: &str, bit_offset: i64, bit_size: i64, value: bool) -> Operatio
 
     Operation {
         op: OperationType::BitRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -734,8 +736,8 @@ pub fn rscan(bin: &str, bit_offset: i64, bit_size: i64, value: bool) -> Operatio
 
     Operation {
         op: OperationType::BitRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -765,8 +767,8 @@ pub fn get_int(bin: &str, bit_offset: i64, bit_size: i64, signed: bool) -> Opera
 
     Operation {
         op: OperationType::BitRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtBitOp(cdt_op),
     }
 }
@@ -13,6 +13,7 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::commands::buffer::Buffer;
@@ -25,11 +26,27 @@ pub enum CdtArgument<'a> {
     Byte(u8),
     Int(i64),
     Bool(bool),
-    Value(&'a Value),
-    List(&'a [Value]),
-    Map(&'a HashMap<Value, Value>),
+    Value(Cow<'a, Value>),
+    List(Cow<'a, [Value]>),
+    Map(Cow<'a, HashMap<Value, Value>>),
 }
 
+impl<'a> CdtArgument<'a> {
+    pub fn into_owned(self) -> CdtArgument<'static> {
+        match self {
+            CdtArgument::Byte(val) => CdtArgument::Byte(val),
+            CdtArgument::Int(val) => CdtArgument::Int(val),
+            CdtArgument::Bool(val) => CdtArgument::Bool(val),
+            CdtArgument::Value(val) => CdtArgument::Value(Cow::Owned(val.into_owned())),
+            CdtArgument::List(val) => CdtArgument::List(Cow::Owned(val.into_owned())),
+            CdtArgument::Map(val) => CdtArgument::Map(Cow::Owned(val.into_owned())),
+        }
+    }
+}
+
+// Attribute flag asking the server to persist the index of a top-level list or map.
+pub(crate) const PERSIST_INDEX_FLAG: u8 = 0x10;
+
 pub type OperationEncoder = Box<
     dyn Fn(&mut Option<&mut Buffer>, &CdtOperation, &[CdtContext]) -> usize + Send + Sync + 'static,
 >;
@@ -55,4 +72,12 @@ impl<'a> CdtOperation<'a> {
         let size: usize = (self.encoder)(&mut Some(buffer), self, ctx);
         size
     }
+
+    pub fn into_owned(self) -> CdtOperation<'static> {
+        CdtOperation {
+            op: self.op,
+            encoder: self.encoder,
+            args: self.args.into_iter().map(CdtArgument::into_owned).collect(),
+        }
+    }
 }
@@ -13,6 +13,9 @@
 // limitations under the License.
 
 //! Operation Context for nested Operations
+use crate::commands::buffer::Buffer;
+use crate::errors::{ErrorKind, Result};
+use crate::msgpack::{decoder, encoder};
 use crate::operations::lists::{list_order_flag, ListOrderType};
 use crate::operations::maps::map_order_flag;
 use crate::operations::MapOrder;
@@ -48,6 +51,119 @@ pub struct CdtContext {
     pub value: Value,
 }
 
+// Flags that can be combined with a context type. They define the order of a list or map that
+// is created at the context level.
+const CTX_FLAGS_MASK: u8 = 0xc0;
+
+impl CdtContext {
+    /// Serializes a context path into the base64 encoded msgpack form used by the server, e.g.
+    /// for the `context` parameter of secondary index creation. The path can be restored with
+    /// `CdtContext::from_base64`.
+    pub fn to_base64(ctx: &[CdtContext]) -> String {
+        let size = encoder::pack_cdt_context(&mut None, ctx);
+        let mut buf = Buffer::new(size);
+        buf.data_buffer.resize(size, 0);
+        encoder::pack_cdt_context(&mut Some(&mut buf), ctx);
+        base64::encode(&buf.data_buffer)
+    }
+
+    /// Restores a context path from its base64 encoded msgpack form, as created by
+    /// `CdtContext::to_base64`.
+    pub fn from_base64(ctx: &str) -> Result<Vec<CdtContext>> {
+        let mut buf = Buffer::new(0);
+        buf.data_buffer = base64::decode(ctx)?;
+        if buf.data_buffer.is_empty() || !matches!(buf.peek(), 0x90..=0x9f | 0xdc | 0xdd) {
+            bail!(ErrorKind::InvalidArgument(
+                "Context must be a msgpack list".to_string()
+            ));
+        }
+        // The decoder expects well-formed server responses, so the input is checked first.
+        if msgpack_item_end(&buf.data_buffer, 0) != Some(buf.data_buffer.len()) {
+            bail!(ErrorKind::InvalidArgument(
+                "Context is not valid msgpack".to_string()
+            ));
+        }
+
+        let Value::List(items) = decoder::unpack_value_list(&mut buf)? else {
+            bail!(ErrorKind::InvalidArgument(
+                "Context must be a msgpack list".to_string()
+            ));
+        };
+        if items.len() % 2 != 0 {
+            bail!(ErrorKind::InvalidArgument(
+                "Context list must contain pairs of type and value".to_string()
+            ));
+        }
+
+        let mut res = Vec::with_capacity(items.len() / 2);
+        let mut items = items.into_iter();
+        while let (Some(id), Some(value)) = (items.next(), items.next()) {
+            let id = match id {
+                Value::Int(id) if (0..=0xff).contains(&id) => id as u8,
+                _ => bail!(ErrorKind::InvalidArgument(format!(
+                    "Invalid context type: {}",
+                    id
+                ))),
+            };
+            res.push(CdtContext {
+                id: id & !CTX_FLAGS_MASK,
+                flags: id & CTX_FLAGS_MASK,
+                value,
+            });
+        }
+        Ok(res)
+    }
+}
+
+// Returns the offset following the msgpack item that starts at `offset`, or `None` if the item is
+// truncated or cannot be decoded. Strings and blobs must hold at least the particle type byte.
+fn msgpack_item_end(data: &[u8], offset: usize) -> Option<usize> {
+    let read_len = |offset: usize, size: usize| {
+        let bytes = data.get(offset..offset + size)?;
+        Some(bytes.iter().fold(0, |len, b| (len << 8) | *b as usize))
+    };
+    let blob_end = |offset: usize, len: usize| if len == 0 { None } else { Some(offset + len) };
+    let items_end = |mut offset: usize, count: usize| {
+        for _ in 0..count {
+            offset = msgpack_item_end(data, offset)?;
+        }
+        Some(offset)
+    };
+
+    let obj_type = *data.get(offset)?;
+    let offset = offset + 1;
+    let end = match obj_type {
+        0x00..=0x7f | 0xc0 | 0xc2 | 0xc3 | 0xe0..=0xff => offset,
+        0x80..=0x8f => items_end(offset, 2 * (obj_type & 0x0f) as usize)?,
+        0x90..=0x9f => items_end(offset, (obj_type & 0x0f) as usize)?,
+        0xa0..=0xbf => blob_end(offset, (obj_type & 0x1f) as usize)?,
+        0xc1 => return None,
+        0xc4 | 0xd9 => blob_end(offset + 1, read_len(offset, 1)?)?,
+        0xc5 | 0xda => blob_end(offset + 2, read_len(offset, 2)?)?,
+        0xc6 | 0xdb => blob_end(offset + 4, read_len(offset, 4)?)?,
+        0xc7 => offset + 2 + read_len(offset, 1)?,
+        0xc8 => offset + 3 + read_len(offset, 2)?,
+        0xc9 => offset + 5 + read_len(offset, 4)?,
+        0xcc | 0xd0 => offset + 1,
+        0xcd | 0xd1 | 0xd4 => offset + 2,
+        0xd5 => offset + 3,
+        0xca | 0xce | 0xd2 => offset + 4,
+        0xd6 => offset + 5,
+        0xcb | 0xcf | 0xd3 => offset + 8,
+        0xd7 => offset + 9,
+        0xd8 => offset + 17,
+        0xdc => items_end(offset + 2, read_len(offset, 2)?)?,
+        0xdd => items_end(offset + 4, read_len(offset, 4)?)?,
+        0xde => items_end(offset + 2, 2 * read_len(offset, 2)?)?,
+        0xdf => items_end(offset + 4, 2 * read_len(offset, 4)?)?,
+    };
+    if end <= data.len() {
+        Some(end)
+    } else {
+        None
+    }
+}
+
 /// Defines Lookup list by index offset.
 /// If the index is negative, the resolved index starts backwards from end of list.
 /// If an index is out of bounds, a parameter error will be returned.
@@ -147,3 +263,58 @@ pub const fn ctx_map_value(key: Value) -> CdtContext {
         value: key,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ctx_list_index, ctx_map_key, ctx_map_key_create, CdtContext};
+    use crate::operations::MapOrder;
+    use crate::Value;
+
+    #[test]
+    fn base64_round_trip() {
+        let ctx = vec![
+            ctx_map_key(Value::from("profile")),
+            ctx_map_key_create(Value::from("address"), MapOrder::KeyOrdered),
+            ctx_list_index(-1),
+        ];
+        let encoded = CdtContext::to_base64(&ctx);
+        let decoded = CdtContext::from_base64(&encoded).unwrap();
+
+        assert_eq!(decoded.len(), ctx.len());
+        for (a, b) in ctx.iter().zip(decoded.iter()) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.flags, b.flags);
+            assert_eq!(a.value, b.value);
+        }
+
+        assert!(CdtContext::from_base64("kRA=").is_err());
+        assert!(CdtContext::from_base64("not base64").is_err());
+    }
+
+    #[test]
+    fn base64_malformed() {
+        // truncated lists and values
+        assert!(CdtContext::from_base64("kg==").is_err());
+        assert!(CdtContext::from_base64("kwE=").is_err());
+        assert!(CdtContext::from_base64("kgHZ").is_err());
+        let encoded = CdtContext::to_base64(&[ctx_map_key(Value::from("profile"))]);
+        let mut bytes = base64::decode(encoded).unwrap();
+        bytes.pop();
+        assert!(CdtContext::from_base64(&base64::encode(&bytes)).is_err());
+
+        // garbage: not a list, invalid type byte, empty string, trailing bytes
+        assert!(CdtContext::from_base64("").is_err());
+        assert!(CdtContext::from_base64("AQ==").is_err());
+        assert!(CdtContext::from_base64(&base64::encode([0x92, 0x10, 0xc1])).is_err());
+        assert!(CdtContext::from_base64(&base64::encode([0x92, 0x22, 0xa0])).is_err());
+        assert!(CdtContext::from_base64(&base64::encode([0x92, 0x10, 0x01, 0x01])).is_err());
+        assert!(CdtContext::from_base64(&base64::encode([0xdd, 0xff, 0xff, 0xff, 0xff])).is_err());
+        assert!(CdtContext::from_base64(&base64::encode([0x92, 0x10, 0xc7, 0xff, 0x01])).is_err());
+
+        // extensions decode as nil
+        let mut bytes = vec![0x92, 0x10, 0xc7, 0xff, 0x01];
+        bytes.resize(bytes.len() + 0xff, 0);
+        let decoded = CdtContext::from_base64(&base64::encode(&bytes)).unwrap();
+        assert_eq!(decoded[0].value, Value::Nil);
+    }
+}
@@ -16,6 +16,8 @@
 //! Expression Operations.
 //! This functions allow users to run `FilterExpressions` as Operate commands.
 
+use std::borrow::Cow;
+
 use crate::commands::buffer::Buffer;
 use crate::expressions::FilterExpression;
 use crate::msgpack::encoder::{pack_array_begin, pack_integer};
@@ -74,7 +76,7 @@ pub type ExpressionEncoder =
 pub struct ExpOperation<'a> {
     pub encoder: ExpressionEncoder,
     pub policy: i64,
-    pub exp: &'a FilterExpression,
+    pub exp: Cow<'a, FilterExpression>,
 }
 
 impl<'a> ExpOperation<'a> {
@@ -92,6 +94,14 @@ impl<'a> ExpOperation<'a> {
         let size: usize = (self.encoder)(&mut Some(buffer), self);
         size
     }
+    #[doc(hidden)]
+    pub fn into_owned(self) -> ExpOperation<'static> {
+        ExpOperation {
+            encoder: self.encoder,
+            policy: self.policy,
+            exp: Cow::Owned(self.exp.into_owned()),
+        }
+    }
 }
 
 /// Expression read Flags
@@ -134,12 +144,12 @@ pub fn write_exp<'a, E: ToExpWriteFlagBitmask>(
     let op = ExpOperation {
         encoder: Box::new(pack_write_exp),
         policy: flags.to_bitmask(),
-        exp,
+        exp: Cow::Borrowed(exp),
     };
     Operation {
         op: OperationType::ExpWrite,
-        ctx: &[],
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(&[]),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::EXPOp(op),
     }
 }
@@ -153,12 +163,12 @@ pub fn read_exp<'a, E: ToExpReadFlagBitmask>(
     let op = ExpOperation {
         encoder: Box::new(pack_read_exp),
         policy: flags.to_bitmask(),
-        exp,
+        exp: Cow::Borrowed(exp),
     };
     Operation {
         op: OperationType::ExpRead,
-        ctx: &[],
-        bin: OperationBin::Name(name),
+        ctx: Cow::Borrowed(&[]),
+        bin: OperationBin::Name(Cow::Borrowed(name)),
         data: OperationData::EXPOp(op),
     }
 }
@@ -16,6 +16,8 @@
 //! `HyperLogLog` operations on HLL items nested in lists/maps are not currently
 //! supported by the server.
 
+use std::borrow::Cow;
+
 use crate::msgpack::encoder::pack_hll_op;
 use crate::operations::cdt::{CdtArgument, CdtOperation};
 use crate::operations::cdt_context::DEFAULT_CTX;
@@ -134,8 +136,8 @@ pub fn init_with_min_hash<'a>(
     };
     Operation {
         op: OperationType::HllWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -173,7 +175,7 @@ pub fn add_with_index_and_min_hash<'a>(
         op: HLLOpType::Add as u8,
         encoder: Box::new(pack_hll_op),
         args: vec![
-            CdtArgument::List(list),
+            CdtArgument::List(Cow::Borrowed(list)),
             CdtArgument::Int(index_bit_count),
             CdtArgument::Int(min_hash_bit_count),
             CdtArgument::Byte(policy.flags as u8),
@@ -181,8 +183,8 @@ pub fn add_with_index_and_min_hash<'a>(
     };
     Operation {
         op: OperationType::HllWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -195,14 +197,14 @@ pub fn set_union<'a>(policy: &HLLPolicy, bin: &'a str, list: &'a [Value]) -> Ope
         op: HLLOpType::SetUnion as u8,
         encoder: Box::new(pack_hll_op),
         args: vec![
-            CdtArgument::List(list),
+            CdtArgument::List(Cow::Borrowed(list)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
     Operation {
         op: OperationType::HllWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -217,8 +219,8 @@ pub fn refresh_count(bin: &str) -> Operation {
     };
     Operation {
         op: OperationType::HllWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -235,8 +237,8 @@ pub fn fold(bin: &str, index_bit_count: i64) -> Operation {
     };
     Operation {
         op: OperationType::HllWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -251,8 +253,8 @@ pub fn get_count(bin: &str) -> Operation {
     };
     Operation {
         op: OperationType::HllRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -264,12 +266,12 @@ pub fn get_union<'a>(bin: &'a str, list: &'a [Value]) -> Operation<'a> {
     let cdt_op = CdtOperation {
         op: HLLOpType::Union as u8,
         encoder: Box::new(pack_hll_op),
-        args: vec![CdtArgument::List(list)],
+        args: vec![CdtArgument::List(Cow::Borrowed(list))],
     };
     Operation {
         op: OperationType::HllRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -281,12 +283,12 @@ pub fn get_union_count<'a>(bin: &'a str, list: &'a [Value]) -> Operation<'a> {
     let cdt_op = CdtOperation {
         op: HLLOpType::UnionCount as u8,
         encoder: Box::new(pack_hll_op),
-        args: vec![CdtArgument::List(list)],
+        args: vec![CdtArgument::List(Cow::Borrowed(list))],
     };
     Operation {
         op: OperationType::HllRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -298,12 +300,12 @@ pub fn get_intersect_count<'a>(bin: &'a str, list: &'a [Value]) -> Operation<'a>
     let cdt_op = CdtOperation {
         op: HLLOpType::IntersectCount as u8,
         encoder: Box::new(pack_hll_op),
-        args: vec![CdtArgument::List(list)],
+        args: vec![CdtArgument::List(Cow::Borrowed(list))],
     };
     Operation {
         op: OperationType::HllRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -314,12 +316,12 @@ pub fn get_similarity<'a>(bin: &'a str, list: &'a [Value]) -> Operation<'a> {
     let cdt_op = CdtOperation {
         op: HLLOpType::Similarity as u8,
         encoder: Box::new(pack_hll_op),
-        args: vec![CdtArgument::List(list)],
+        args: vec![CdtArgument::List(Cow::Borrowed(list))],
     };
     Operation {
         op: OperationType::HllRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
@@ -335,8 +337,8 @@ pub fn describe(bin: &str) -> Operation {
     };
     Operation {
         op: OperationType::HllRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::HLLOp(cdt_op),
     }
 }
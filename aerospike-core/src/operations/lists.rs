@@ -31,8 +31,10 @@
 //! If an index is out of bounds, a parameter error will be returned. If a range is partially out of
 //! bounds, the valid part of the range will be returned.
 
-use crate::msgpack::encoder::pack_cdt_op;
-use crate::operations::cdt::{CdtArgument, CdtOperation};
+use std::borrow::Cow;
+
+use crate::msgpack::encoder::{pack_cdt_create_op, pack_cdt_op};
+use crate::operations::cdt::{CdtArgument, CdtOperation, PERSIST_INDEX_FLAG};
 use crate::operations::cdt_context::{CdtContext, DEFAULT_CTX};
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
 use crate::Value;
@@ -112,6 +114,9 @@ pub enum ListReturnType {
     Count = 5,
     /// Return value for single key read and value list for range read.
     Values = 7,
+    /// Return true if count of items selected is greater than zero. The result is returned as
+    /// `Value::Bool`.
+    Exists = 13,
     /// Invert meaning of list command and return values.
     /// With the INVERTED flag enabled, the items outside of the specified index range will be returned.
     /// The meaning of the list command can also be inverted.
@@ -247,19 +252,32 @@ pub const fn list_order_flag(order: ListOrderType, pad: bool) -> u8 {
 /// Server creates list at given context level. The context is allowed to be beyond list
 /// boundaries only if pad is set to true.  In that case, nil list entries will be inserted to
 /// satisfy the context position.
-pub fn create(bin: &str, list_order: ListOrderType, pad: bool) -> Operation {
+///
+/// If the context is empty, the order of the top-level list bin is set instead. In that case,
+/// `persist_index` makes the server persist the list index, which speeds up lookups by index and
+/// rank on large lists. The index can only be persisted for top-level lists, so `persist_index`
+/// is ignored for nested lists.
+pub fn create<'a>(
+    bin: &'a str,
+    list_order: ListOrderType,
+    pad: bool,
+    persist_index: bool,
+    ctx: &'a [CdtContext],
+) -> Operation<'a> {
+    let mut attributes = list_order as u8;
+    if ctx.is_empty() && persist_index {
+        attributes |= PERSIST_INDEX_FLAG;
+    }
+    let flag = list_order_flag(list_order, pad);
     let cdt_op = CdtOperation {
         op: CdtListOpType::SetType as u8,
-        encoder: Box::new(pack_cdt_op),
-        args: vec![
-            CdtArgument::Byte(list_order_flag(list_order, pad)),
-            CdtArgument::Byte(list_order as u8),
-        ],
+        encoder: Box::new(move |buf, cdt_op, ctx| pack_cdt_create_op(buf, cdt_op, ctx, flag)),
+        args: vec![CdtArgument::Byte(attributes)],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(ctx),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -278,8 +296,8 @@ pub fn set_order<'a>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(ctx),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -290,15 +308,15 @@ pub fn append<'a>(policy: &ListPolicy, bin: &'a str, value: &'a Value) -> Operat
         op: CdtListOpType::Append as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Byte(policy.attributes as u8),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -315,15 +333,15 @@ pub fn append_items<'a>(policy: &ListPolicy, bin: &'a str, values: &'a [Value])
         op: CdtListOpType::AppendItems as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::List(values),
+            CdtArgument::List(Cow::Borrowed(values)),
             CdtArgument::Byte(policy.attributes as u8),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -341,14 +359,14 @@ pub fn insert<'a>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(index),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -371,14 +389,14 @@ pub fn insert_items<'a>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(index),
-            CdtArgument::List(values),
+            CdtArgument::List(Cow::Borrowed(values)),
             CdtArgument::Byte(policy.flags as u8),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -393,8 +411,8 @@ pub fn pop(bin: &str, index: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -409,8 +427,8 @@ pub fn pop_range(bin: &str, index: i64, count: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -425,8 +443,8 @@ pub fn pop_range_from(bin: &str, index: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -441,8 +459,8 @@ pub fn remove(bin: &str, index: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -457,8 +475,8 @@ pub fn remove_range(bin: &str, index: i64, count: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -473,8 +491,8 @@ pub fn remove_range_from(bin: &str, index: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -491,13 +509,13 @@ pub fn remove_by_value<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -514,13 +532,13 @@ pub fn remove_by_value_list<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::List(values),
+            CdtArgument::List(Cow::Borrowed(values)),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -541,14 +559,14 @@ pub fn remove_by_value_range<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(begin),
-            CdtArgument::Value(end),
+            CdtArgument::Value(Cow::Borrowed(begin)),
+            CdtArgument::Value(Cow::Borrowed(end)),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -578,14 +596,14 @@ pub fn remove_by_value_relative_rank_range<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -616,22 +634,26 @@ pub fn remove_by_value_relative_rank_range_count<'a, TLR: ToListReturnTypeBitmas
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
 
 /// Creates a list remove operation.
 /// Server removes list item identified by index and returns removed data specified by returnType.
-pub fn remove_by_index<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, return_type: TLR) -> Operation {
+pub fn remove_by_index<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::RemoveByIndex as u8,
         encoder: Box::new(pack_cdt_op),
@@ -642,8 +664,8 @@ pub fn remove_by_index<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, retu
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -651,7 +673,11 @@ pub fn remove_by_index<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, retu
 /// Creates a list remove operation.
 /// Server removes list items starting at specified index to the end of list and returns removed
 /// data specified by returnType.
-pub fn remove_by_index_range<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, return_type: TLR) -> Operation {
+pub fn remove_by_index_range<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::RemoveByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -662,8 +688,8 @@ pub fn remove_by_index_range<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -687,15 +713,19 @@ pub fn remove_by_index_range_count<TLR: ToListReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
 
 /// Creates a list remove operation.
 /// Server removes list item identified by rank and returns removed data specified by returnType.
-pub fn remove_by_rank<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return_type: TLR) -> Operation {
+pub fn remove_by_rank<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::RemoveByRank as u8,
         encoder: Box::new(pack_cdt_op),
@@ -703,8 +733,8 @@ pub fn remove_by_rank<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -712,7 +742,11 @@ pub fn remove_by_rank<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return
 /// Creates a list remove operation.
 /// Server removes list items starting at specified rank to the last ranked item and returns removed
 /// data specified by returnType.
-pub fn remove_by_rank_range<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return_type: TLR) -> Operation {
+pub fn remove_by_rank_range<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::RemoveByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -720,8 +754,8 @@ pub fn remove_by_rank_range<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64,
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -745,8 +779,8 @@ pub fn remove_by_rank_range_count<TLR: ToListReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -762,12 +796,12 @@ pub fn set<'a>(bin: &'a str, index: i64, value: &'a Value) -> Operation<'a> {
     let cdt_op = CdtOperation {
         op: CdtListOpType::Set as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Int(index), CdtArgument::Value(value)],
+        args: vec![CdtArgument::Int(index), CdtArgument::Value(Cow::Borrowed(value))],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -783,8 +817,8 @@ pub fn trim(bin: &str, index: i64, count: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -799,8 +833,8 @@ pub fn clear(bin: &str) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -819,8 +853,8 @@ pub fn increment<'a>(policy: &ListPolicy, bin: &'a str, index: i64, value: i64)
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -834,8 +868,8 @@ pub fn size(bin: &str) -> Operation {
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -849,8 +883,8 @@ pub fn get(bin: &str, index: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -865,8 +899,8 @@ pub fn get_range(bin: &str, index: i64, count: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -881,8 +915,8 @@ pub fn get_range_from(bin: &str, index: i64) -> Operation {
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -899,14 +933,14 @@ pub fn get_by_value<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
         ],
     };
 
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -923,13 +957,13 @@ pub fn get_by_value_list<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::List(values),
+            CdtArgument::List(Cow::Borrowed(values)),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -950,21 +984,25 @@ pub fn get_by_value_range<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(begin),
-            CdtArgument::Value(end),
+            CdtArgument::Value(Cow::Borrowed(begin)),
+            CdtArgument::Value(Cow::Borrowed(end)),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
 
 /// Creates list get by index operation.
 /// Server selects list item identified by index and returns selected data specified by returnType
-pub fn get_by_index<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, return_type: TLR) -> Operation {
+pub fn get_by_index<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::GetByIndex as u8,
         encoder: Box::new(pack_cdt_op),
@@ -976,8 +1014,8 @@ pub fn get_by_index<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, return_
 
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -985,7 +1023,11 @@ pub fn get_by_index<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, return_
 /// Creates list get by index range operation.
 /// Server selects list items starting at specified index to the end of list and returns selected
 /// data specified by returnType.
-pub fn get_by_index_range<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, return_type: TLR) -> Operation {
+pub fn get_by_index_range<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::GetByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -997,8 +1039,8 @@ pub fn get_by_index_range<TLR: ToListReturnTypeBitmask>(bin: &str, index: i64, r
 
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -1024,15 +1066,19 @@ pub fn get_by_index_range_count<TLR: ToListReturnTypeBitmask>(
 
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
 
 /// Creates a list get by rank operation.
 /// Server selects list item identified by rank and returns selected data specified by returnType.
-pub fn get_by_rank<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return_type: TLR) -> Operation {
+pub fn get_by_rank<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::GetByRank as u8,
         encoder: Box::new(pack_cdt_op),
@@ -1040,8 +1086,8 @@ pub fn get_by_rank<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return_ty
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -1049,7 +1095,11 @@ pub fn get_by_rank<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return_ty
 /// Creates a list get by rank range operation.
 /// Server selects list items starting at specified rank to the last ranked item and returns selected
 /// data specified by returnType.
-pub fn get_by_rank_range<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, return_type: TLR) -> Operation {
+pub fn get_by_rank_range<TLR: ToListReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TLR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::GetByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -1057,8 +1107,8 @@ pub fn get_by_rank_range<TLR: ToListReturnTypeBitmask>(bin: &str, rank: i64, ret
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -1082,8 +1132,8 @@ pub fn get_by_rank_range_count<TLR: ToListReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -1113,14 +1163,14 @@ pub fn get_by_value_relative_rank_range<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -1151,15 +1201,15 @@ pub fn get_by_value_relative_rank_range_count<'a, TLR: ToListReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -1175,8 +1225,8 @@ pub fn sort(bin: &str, sort_flags: ListSortFlags) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtListOp(cdt_op),
     }
 }
@@ -40,9 +40,11 @@
 //! * Rank 1 Count 2: Second and third lowest ranked items in map.
 //! * Rank -3 Count 3: Top three ranked items in map.
 
-use crate::msgpack::encoder::pack_cdt_op;
-use crate::operations::cdt::{CdtArgument, CdtOperation};
-use crate::operations::cdt_context::DEFAULT_CTX;
+use std::borrow::Cow;
+
+use crate::msgpack::encoder::{pack_cdt_create_op, pack_cdt_op};
+use crate::operations::cdt::{CdtArgument, CdtOperation, PERSIST_INDEX_FLAG};
+use crate::operations::cdt_context::{CdtContext, DEFAULT_CTX};
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
 use crate::Value;
 use std::collections::HashMap;
@@ -147,6 +149,16 @@ pub enum MapReturnType {
     /// * `Value::OrderedMap`: Returned for range results where range order needs to be preserved.
     KeyValue = 8,
 
+    /// Return true if count of items selected is greater than zero. The result is returned as
+    /// `Value::Bool`.
+    Exists = 13,
+
+    /// Return key/value items as a map without a defined order, returned as `Value::HashMap`.
+    UnorderedMap = 16,
+
+    /// Return key/value items as a key ordered map, returned as `Value::OrderedMap`.
+    OrderedMap = 17,
+
     /// Invert meaning of map command and return values.
     /// With the INVERTED flag enabled, the keys outside of the specified key range will be removed and returned.
     Inverted = 0x10000,
@@ -306,8 +318,39 @@ pub fn set_order(bin: &str, map_order: MapOrder) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
+        data: OperationData::CdtMapOp(cdt_op),
+    }
+}
+
+/// Create map create operation. Server creates a map with the given order at the given context
+/// level. Server does not return a result.
+///
+/// If the context is empty, the order of the top-level map bin is set instead. In that case,
+/// `persist_index` makes the server persist the map index, which speeds up lookups by key and
+/// rank on large maps. The index can only be persisted for top-level maps, so `persist_index`
+/// is ignored for nested maps.
+pub fn create<'a>(
+    bin: &'a str,
+    map_order: MapOrder,
+    persist_index: bool,
+    ctx: &'a [CdtContext],
+) -> Operation<'a> {
+    let mut attributes = map_order as u8;
+    if ctx.is_empty() && persist_index {
+        attributes |= PERSIST_INDEX_FLAG;
+    }
+    let flag = map_order_flag(map_order);
+    let cdt_op = CdtOperation {
+        op: CdtMapOpType::SetType as u8,
+        encoder: Box::new(move |buf, cdt_op, ctx| pack_cdt_create_op(buf, cdt_op, ctx, flag)),
+        args: vec![CdtArgument::Byte(attributes)],
+    };
+    Operation {
+        op: OperationType::CdtWrite,
+        ctx: Cow::Borrowed(ctx),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -323,9 +366,9 @@ pub fn put<'a>(
     key: &'a Value,
     val: &'a Value,
 ) -> Operation<'a> {
-    let mut args = vec![CdtArgument::Value(key)];
+    let mut args = vec![CdtArgument::Value(Cow::Borrowed(key))];
     if !val.is_nil() {
-        args.push(CdtArgument::Value(val));
+        args.push(CdtArgument::Value(Cow::Borrowed(val)));
     }
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
@@ -346,8 +389,8 @@ pub fn put<'a>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -363,7 +406,7 @@ pub fn put_items<'a>(
     bin: &'a str,
     items: &'a HashMap<Value, Value>,
 ) -> Operation<'a> {
-    let mut args = vec![CdtArgument::Map(items)];
+    let mut args = vec![CdtArgument::Map(Cow::Borrowed(items))];
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
     }
@@ -374,8 +417,8 @@ pub fn put_items<'a>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -391,9 +434,9 @@ pub fn increment_value<'a>(
     key: &'a Value,
     incr: &'a Value,
 ) -> Operation<'a> {
-    let mut args = vec![CdtArgument::Value(key)];
+    let mut args = vec![CdtArgument::Value(Cow::Borrowed(key))];
     if !incr.is_nil() {
-        args.push(CdtArgument::Value(incr));
+        args.push(CdtArgument::Value(Cow::Borrowed(incr)));
     }
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
@@ -405,8 +448,8 @@ pub fn increment_value<'a>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -422,9 +465,9 @@ pub fn decrement_value<'a>(
     key: &'a Value,
     decr: &'a Value,
 ) -> Operation<'a> {
-    let mut args = vec![CdtArgument::Value(key)];
+    let mut args = vec![CdtArgument::Value(Cow::Borrowed(key))];
     if !decr.is_nil() {
-        args.push(CdtArgument::Value(decr));
+        args.push(CdtArgument::Value(Cow::Borrowed(decr)));
     }
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
@@ -436,8 +479,8 @@ pub fn decrement_value<'a>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -452,8 +495,8 @@ pub fn clear(bin: &str) -> Operation {
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -470,13 +513,13 @@ pub fn remove_by_key<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(key),
+            CdtArgument::Value(Cow::Borrowed(key)),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -493,13 +536,13 @@ pub fn remove_by_key_list<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::List(keys),
+            CdtArgument::List(Cow::Borrowed(keys)),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -516,10 +559,10 @@ pub fn remove_by_key_range<'a, TMR: ToMapReturnTypeBitmask>(
 ) -> Operation<'a> {
     let mut args = vec![
         CdtArgument::Int(return_type.to_bitmask()),
-        CdtArgument::Value(begin),
+        CdtArgument::Value(Cow::Borrowed(begin)),
     ];
     if !end.is_nil() {
-        args.push(CdtArgument::Value(end));
+        args.push(CdtArgument::Value(Cow::Borrowed(end)));
     }
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByKeyInterval as u8,
@@ -528,8 +571,8 @@ pub fn remove_by_key_range<'a, TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -546,13 +589,13 @@ pub fn remove_by_value<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -569,13 +612,13 @@ pub fn remove_by_value_list<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::List(values),
+            CdtArgument::List(Cow::Borrowed(values)),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -592,10 +635,10 @@ pub fn remove_by_value_range<'a, TMR: ToMapReturnTypeBitmask>(
 ) -> Operation<'a> {
     let mut args = vec![
         CdtArgument::Int(return_type.to_bitmask()),
-        CdtArgument::Value(begin),
+        CdtArgument::Value(Cow::Borrowed(begin)),
     ];
     if !end.is_nil() {
-        args.push(CdtArgument::Value(end));
+        args.push(CdtArgument::Value(Cow::Borrowed(end)));
     }
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByValueInterval as u8,
@@ -604,15 +647,19 @@ pub fn remove_by_value_range<'a, TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
 
 /// Create map remove operation. Server removes the map item identified by the index and return
 /// the removed data specified by `return_type`.
-pub fn remove_by_index<TMR: ToMapReturnTypeBitmask>(bin: &str, index: i64, return_type: TMR) -> Operation {
+pub fn remove_by_index<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByIndex as u8,
         encoder: Box::new(pack_cdt_op),
@@ -623,8 +670,8 @@ pub fn remove_by_index<TMR: ToMapReturnTypeBitmask>(bin: &str, index: i64, retur
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -648,15 +695,19 @@ pub fn remove_by_index_range<TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
 
 /// Create map remove operation. Server removes the map items starting at the specified index
 /// to the end of the map and returns the removed data specified by `return_type`.
-pub fn remove_by_index_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, index: i64, return_type: TMR) -> Operation {
+pub fn remove_by_index_range_from<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -667,15 +718,19 @@ pub fn remove_by_index_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, index:
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
 
 /// Create map remove operation. Server removes the map item identified by rank and returns the
 /// removed data specified by `return_type`.
-pub fn remove_by_rank<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i64, return_type: TMR) -> Operation {
+pub fn remove_by_rank<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByRank as u8,
         encoder: Box::new(pack_cdt_op),
@@ -683,8 +738,8 @@ pub fn remove_by_rank<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i64, return_
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -708,15 +763,19 @@ pub fn remove_by_rank_range<TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
 
 /// Create map remove operation. Server removes the map items starting at the specified rank to
 /// the last ranked item and returns the removed data specified by `return_type`.
-pub fn remove_by_rank_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i64, return_type: TMR) -> Operation {
+pub fn remove_by_rank_range_from<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -724,8 +783,8 @@ pub fn remove_by_rank_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -739,27 +798,31 @@ pub fn size(bin: &str) -> Operation {
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
 
 /// Create map get by key operation. Server selects the map item identified by the key and
 /// returns the selected data specified by `return_type`.
-pub fn get_by_key<'a, TMR: ToMapReturnTypeBitmask>(bin: &'a str, key: &'a Value, return_type: TMR) -> Operation<'a> {
+pub fn get_by_key<'a, TMR: ToMapReturnTypeBitmask>(
+    bin: &'a str,
+    key: &'a Value,
+    return_type: TMR,
+) -> Operation<'a> {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByKey as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(key),
+            CdtArgument::Value(Cow::Borrowed(key)),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -776,10 +839,10 @@ pub fn get_by_key_range<'a, TMR: ToMapReturnTypeBitmask>(
 ) -> Operation<'a> {
     let mut args = vec![
         CdtArgument::Int(return_type.to_bitmask()),
-        CdtArgument::Value(begin),
+        CdtArgument::Value(Cow::Borrowed(begin)),
     ];
     if !end.is_nil() {
-        args.push(CdtArgument::Value(end));
+        args.push(CdtArgument::Value(Cow::Borrowed(end)));
     }
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByKeyInterval as u8,
@@ -788,8 +851,8 @@ pub fn get_by_key_range<'a, TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -806,13 +869,13 @@ pub fn get_by_value<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -829,10 +892,10 @@ pub fn get_by_value_range<'a, TMR: ToMapReturnTypeBitmask>(
 ) -> Operation<'a> {
     let mut args = vec![
         CdtArgument::Int(return_type.to_bitmask()),
-        CdtArgument::Value(begin),
+        CdtArgument::Value(Cow::Borrowed(begin)),
     ];
     if !end.is_nil() {
-        args.push(CdtArgument::Value(end));
+        args.push(CdtArgument::Value(Cow::Borrowed(end)));
     }
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByValueInterval as u8,
@@ -841,15 +904,19 @@ pub fn get_by_value_range<'a, TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
 
 /// Create map get by index operation. Server selects the map item identified by index and
 /// returns the selected data specified by `return_type`.
-pub fn get_by_index<TMR: ToMapReturnTypeBitmask>(bin: &str, index: i64, return_type: TMR) -> Operation {
+pub fn get_by_index<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByIndex as u8,
         encoder: Box::new(pack_cdt_op),
@@ -860,8 +927,8 @@ pub fn get_by_index<TMR: ToMapReturnTypeBitmask>(bin: &str, index: i64, return_t
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -885,8 +952,8 @@ pub fn get_by_index_range<TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -894,7 +961,11 @@ pub fn get_by_index_range<TMR: ToMapReturnTypeBitmask>(
 /// Create map get by index range operation. Server selects the map items starting at the
 /// specified index to the end of the map and returns the selected data specified by
 /// `return_type`.
-pub fn get_by_index_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, index: i64, return_type: TMR) -> Operation {
+pub fn get_by_index_range_from<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    index: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -905,15 +976,19 @@ pub fn get_by_index_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, index: i6
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
 
 /// Create map get by rank operation. Server selects the map item identified by rank and
 /// returns the selected data specified by `return_type`.
-pub fn get_by_rank<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i64, return_type: TMR) -> Operation {
+pub fn get_by_rank<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByRank as u8,
         encoder: Box::new(pack_cdt_op),
@@ -921,8 +996,8 @@ pub fn get_by_rank<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i64, return_typ
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -946,8 +1021,8 @@ pub fn get_by_rank_range<TMR: ToMapReturnTypeBitmask>(
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -955,7 +1030,11 @@ pub fn get_by_rank_range<TMR: ToMapReturnTypeBitmask>(
 /// Create map get by rank range operation. Server selects the map items starting at the
 /// specified rank to the last ranked item and returns the selected data specified by
 /// `return_type`.
-pub fn get_by_rank_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i64, return_type: TMR) -> Operation {
+pub fn get_by_rank_range_from<TMR: ToMapReturnTypeBitmask>(
+    bin: &str,
+    rank: i64,
+    return_type: TMR,
+) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
@@ -963,8 +1042,8 @@ pub fn get_by_rank_range_from<TMR: ToMapReturnTypeBitmask>(bin: &str, rank: i64,
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -992,14 +1071,14 @@ pub fn remove_by_key_relative_index_range<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(key),
+            CdtArgument::Value(Cow::Borrowed(key)),
             CdtArgument::Int(index),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1028,15 +1107,15 @@ pub fn remove_by_key_relative_index_range_count<'a, TMR: ToMapReturnTypeBitmask>
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(key),
+            CdtArgument::Value(Cow::Borrowed(key)),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1061,14 +1140,14 @@ pub fn remove_by_value_relative_rank_range<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1094,15 +1173,15 @@ pub fn remove_by_value_relative_rank_range_count<'a, TMR: ToMapReturnTypeBitmask
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
     };
     Operation {
         op: OperationType::CdtWrite,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1119,13 +1198,13 @@ pub fn get_by_key_list<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::List(keys),
+            CdtArgument::List(Cow::Borrowed(keys)),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1142,13 +1221,13 @@ pub fn get_by_value_list<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::List(values),
+            CdtArgument::List(Cow::Borrowed(values)),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1176,14 +1255,14 @@ pub fn get_by_key_relative_index_range<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(key),
+            CdtArgument::Value(Cow::Borrowed(key)),
             CdtArgument::Int(index),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1212,15 +1291,15 @@ pub fn get_by_key_relative_index_range_count<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(key),
+            CdtArgument::Value(Cow::Borrowed(key)),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1245,14 +1324,14 @@ pub fn get_by_value_relative_rank_range<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -1278,15 +1357,15 @@ pub fn get_by_value_relative_rank_range_count<'a, TMR: ToMapReturnTypeBitmask>(
         encoder: Box::new(pack_cdt_op),
         args: vec![
             CdtArgument::Int(return_type.to_bitmask()),
-            CdtArgument::Value(value),
+            CdtArgument::Value(Cow::Borrowed(value)),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
     };
     Operation {
         op: OperationType::CdtRead,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin)),
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
@@ -25,6 +25,8 @@ pub mod lists;
 pub mod maps;
 pub mod scalar;
 
+use std::borrow::Cow;
+
 use self::cdt::CdtOperation;
 pub use self::maps::{MapOrder, MapPolicy, MapReturnType, MapWriteMode};
 pub use self::scalar::*;
@@ -58,7 +60,7 @@ pub enum OperationType {
 #[doc(hidden)]
 pub enum OperationData<'a> {
     None,
-    Value(&'a Value),
+    Value(Cow<'a, Value>),
     CdtListOp(CdtOperation<'a>),
     CdtMapOp(CdtOperation<'a>),
     CdtBitOp(CdtOperation<'a>),
@@ -70,7 +72,31 @@ pub enum OperationData<'a> {
 pub enum OperationBin<'a> {
     None,
     All,
-    Name(&'a str),
+    Name(Cow<'a, str>),
+}
+
+impl<'a> OperationData<'a> {
+    fn into_owned(self) -> OperationData<'static> {
+        match self {
+            OperationData::None => OperationData::None,
+            OperationData::Value(val) => OperationData::Value(Cow::Owned(val.into_owned())),
+            OperationData::CdtListOp(op) => OperationData::CdtListOp(op.into_owned()),
+            OperationData::CdtMapOp(op) => OperationData::CdtMapOp(op.into_owned()),
+            OperationData::CdtBitOp(op) => OperationData::CdtBitOp(op.into_owned()),
+            OperationData::HLLOp(op) => OperationData::HLLOp(op.into_owned()),
+            OperationData::EXPOp(op) => OperationData::EXPOp(op.into_owned()),
+        }
+    }
+}
+
+impl<'a> OperationBin<'a> {
+    fn into_owned(self) -> OperationBin<'static> {
+        match self {
+            OperationBin::None => OperationBin::None,
+            OperationBin::All => OperationBin::All,
+            OperationBin::Name(name) => OperationBin::Name(Cow::Owned(name.into_owned())),
+        }
+    }
 }
 
 /// Database operation definition. This data type is used in the client's `operate()` method.
@@ -81,7 +107,7 @@ pub struct Operation<'a> {
 
     // CDT context for nested types
     #[doc(hidden)]
-    pub ctx: &'a [CdtContext],
+    pub ctx: Cow<'a, [CdtContext]>,
 
     // BinName (Optional) determines the name of bin used in operation.
     #[doc(hidden)]
@@ -97,22 +123,34 @@ impl<'a> Operation<'a> {
     pub fn estimate_size(&self) -> usize {
         let mut size: usize = 0;
         size += match self.bin {
-            OperationBin::Name(bin) => bin.len(),
+            OperationBin::Name(ref bin) => bin.len(),
             OperationBin::None | OperationBin::All => 0,
         };
         size += match self.data {
             OperationData::None => 0,
-            OperationData::Value(value) => value.estimate_size(),
+            OperationData::Value(ref value) => value.estimate_size(),
             OperationData::EXPOp(ref exp_op) => exp_op.estimate_size(),
             OperationData::CdtListOp(ref cdt_op)
             | OperationData::CdtMapOp(ref cdt_op)
             | OperationData::CdtBitOp(ref cdt_op)
-            | OperationData::HLLOp(ref cdt_op) => cdt_op.estimate_size(self.ctx),
+            | OperationData::HLLOp(ref cdt_op) => cdt_op.estimate_size(&self.ctx),
         };
 
         size
     }
 
+    /// Returns true if the operation modifies the record.
+    pub const fn is_write(&self) -> bool {
+        !matches!(
+            self.op,
+            OperationType::Read
+                | OperationType::CdtRead
+                | OperationType::BitRead
+                | OperationType::HllRead
+                | OperationType::ExpRead
+        )
+    }
+
     #[doc(hidden)]
     pub fn write_to(&self, buffer: &mut Buffer) -> usize {
         let mut size: usize = 0;
@@ -127,7 +165,7 @@ impl<'a> Operation<'a> {
             OperationData::None => {
                 size += self.write_op_header_to(buffer, ParticleType::NULL as u8);
             }
-            OperationData::Value(value) => {
+            OperationData::Value(ref value) => {
                 size += self.write_op_header_to(buffer, value.particle_type() as u8);
                 size += value.write_to(buffer);
             }
@@ -136,7 +174,7 @@ impl<'a> Operation<'a> {
             | OperationData::CdtBitOp(ref cdt_op)
             | OperationData::HLLOp(ref cdt_op) => {
                 size += self.write_op_header_to(buffer, cdt_op.particle_type() as u8);
-                size += cdt_op.write_to(buffer, self.ctx);
+                size += cdt_op.write_to(buffer, &self.ctx);
             }
             OperationData::EXPOp(ref exp) => {
                 size += self.write_op_header_to(buffer, ParticleType::BLOB as u8);
@@ -152,7 +190,7 @@ impl<'a> Operation<'a> {
         let mut size = buffer.write_u8(particle_type as u8);
         size += buffer.write_u8(0);
         match self.bin {
-            OperationBin::Name(bin) => {
+            OperationBin::Name(ref bin) => {
                 size += buffer.write_u8(bin.len() as u8);
                 size += buffer.write_str(bin);
             }
@@ -164,8 +202,106 @@ impl<'a> Operation<'a> {
     }
 
     /// Set the context of the operation. Required for nested structures
-    pub const fn set_context(mut self, ctx: &'a [CdtContext]) -> Operation<'a> {
-        self.ctx = ctx;
+    pub fn set_context(mut self, ctx: &'a [CdtContext]) -> Operation<'a> {
+        self.ctx = Cow::Borrowed(ctx);
         self
     }
+
+    /// Converts the operation into one that owns its bin name, values and context, by cloning any
+    /// borrowed data. The returned operation is `Send + 'static`, so that operations can be built
+    /// in one place and executed from a spawned task or kept around to be retried later.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// use aerospike::operations;
+    ///
+    /// fn increment(bin_name: &str, by: i64) -> Vec<operations::Operation<'static>> {
+    ///     let bin = as_bin!(bin_name, by);
+    ///     vec![
+    ///         operations::add(&bin).into_owned(),
+    ///         operations::get_bin(bin_name).into_owned(),
+    ///     ]
+    /// }
+    /// ```
+    pub fn into_owned(self) -> Operation<'static> {
+        Operation {
+            op: self.op,
+            ctx: Cow::Owned(self.ctx.into_owned()),
+            bin: self.bin.into_owned(),
+            data: self.data.into_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::cdt_context::{ctx_list_index, ctx_map_key};
+    use super::exp::{read_exp, ExpReadFlags};
+    use super::lists::{append_items, ListOrderType, ListPolicy};
+    use super::maps::MapOrder;
+    use super::{lists, maps, put, Operation};
+    use crate::commands::buffer::Buffer;
+    use crate::expressions::{int_bin, int_val, lt};
+
+    fn encode(op: &Operation) -> Vec<u8> {
+        let mut buf = Buffer::new(1024);
+        buf.data_buffer = vec![0; op.estimate_size() + 8];
+        op.write_to(&mut buf);
+        buf.data_buffer
+    }
+
+    #[test]
+    fn into_owned() {
+        let bin = as_bin!("bin", 1);
+        let values = vec![as_val!(1), as_val!("a")];
+        let ctx = vec![ctx_list_index(0)];
+        let exp = lt(int_bin("bin".to_string()), int_val(10));
+        let policy = ListPolicy::default();
+        let ops = vec![
+            put(&bin),
+            append_items(&policy, "list", &values).set_context(&ctx),
+            read_exp("exp", &exp, ExpReadFlags::Default),
+        ];
+
+        let expected: Vec<Vec<u8>> = ops.iter().map(encode).collect();
+        let owned: Vec<Operation<'static>> = ops.into_iter().map(Operation::into_owned).collect();
+        drop((bin, values, ctx, exp));
+
+        let encoded = thread::spawn(move || owned.iter().map(encode).collect::<Vec<_>>())
+            .join()
+            .unwrap();
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn create() {
+        // top-level map: the order and the persist index flag are sent as attributes
+        let op = maps::create("bin", MapOrder::KeyOrdered, true, &[]);
+        assert!(encode(&op).ends_with(&[0x00, 0x40, 0x91, 0x11]));
+
+        // nested map: the create flag goes on the context; the index is not persisted
+        let ctx = vec![ctx_map_key(as_val!("a"))];
+        let op = maps::create("bin", MapOrder::KeyOrdered, true, &ctx);
+        let expected = [
+            0x93, 0xcd, 0x00, 0xff, 0x92, 0xcc, 0xa2, 0xa2, 0x03, 0x61, 0x92, 0x40, 0x01,
+        ];
+        assert!(encode(&op).ends_with(&expected));
+
+        // nested list: only the innermost context gets the create flag, with pad
+        let ctx = vec![ctx_map_key(as_val!("a")), ctx_list_index(1)];
+        let op = lists::create("bin", ListOrderType::Unordered, true, true, &ctx);
+        let expected = [
+            0x93, 0xcd, 0x00, 0xff, 0x94, 0x22, 0xa2, 0x03, 0x61, 0xcc, 0x90, 0x01, 0x92, 0x00,
+            0x00,
+        ];
+        assert!(encode(&op).ends_with(&expected));
+
+        let op = lists::create("bin", ListOrderType::Ordered, false, false, &ctx);
+        let expected = [0xcc, 0xd0, 0x01, 0x92, 0x00, 0x01];
+        assert!(encode(&op).ends_with(&expected));
+    }
 }
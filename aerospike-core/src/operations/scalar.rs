@@ -15,6 +15,8 @@
 
 //! String/number bin operations. Create operations used by the client's `operate()` method.
 
+use std::borrow::Cow;
+
 use crate::operations::cdt_context::DEFAULT_CTX;
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
 use crate::Bin;
@@ -23,7 +25,7 @@ use crate::Bin;
 pub const fn get<'a>() -> Operation<'a> {
     Operation {
         op: OperationType::Read,
-        ctx: DEFAULT_CTX,
+        ctx: Cow::Borrowed(DEFAULT_CTX),
         bin: OperationBin::All,
         data: OperationData::None,
     }
@@ -33,7 +35,7 @@ pub const fn get<'a>() -> Operation<'a> {
 pub const fn get_header<'a>() -> Operation<'a> {
     Operation {
         op: OperationType::Read,
-        ctx: DEFAULT_CTX,
+        ctx: Cow::Borrowed(DEFAULT_CTX),
         bin: OperationBin::None,
         data: OperationData::None,
     }
@@ -43,8 +45,8 @@ pub const fn get_header<'a>() -> Operation<'a> {
 pub const fn get_bin(bin_name: &str) -> Operation {
     Operation {
         op: OperationType::Read,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin_name),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin_name)),
         data: OperationData::None,
     }
 }
@@ -53,9 +55,9 @@ pub const fn get_bin(bin_name: &str) -> Operation {
 pub const fn put<'a>(bin: &'a Bin) -> Operation<'a> {
     Operation {
         op: OperationType::Write,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin.name),
-        data: OperationData::Value(&bin.value),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin.name)),
+        data: OperationData::Value(Cow::Borrowed(&bin.value)),
     }
 }
 
@@ -63,9 +65,9 @@ pub const fn put<'a>(bin: &'a Bin) -> Operation<'a> {
 pub const fn append<'a>(bin: &'a Bin) -> Operation<'a> {
     Operation {
         op: OperationType::Append,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin.name),
-        data: OperationData::Value(&bin.value),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin.name)),
+        data: OperationData::Value(Cow::Borrowed(&bin.value)),
     }
 }
 
@@ -73,9 +75,9 @@ pub const fn append<'a>(bin: &'a Bin) -> Operation<'a> {
 pub const fn prepend<'a>(bin: &'a Bin) -> Operation<'a> {
     Operation {
         op: OperationType::Prepend,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin.name),
-        data: OperationData::Value(&bin.value),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin.name)),
+        data: OperationData::Value(Cow::Borrowed(&bin.value)),
     }
 }
 
@@ -83,9 +85,9 @@ pub const fn prepend<'a>(bin: &'a Bin) -> Operation<'a> {
 pub const fn add<'a>(bin: &'a Bin) -> Operation<'a> {
     Operation {
         op: OperationType::Incr,
-        ctx: DEFAULT_CTX,
-        bin: OperationBin::Name(bin.name),
-        data: OperationData::Value(&bin.value),
+        ctx: Cow::Borrowed(DEFAULT_CTX),
+        bin: OperationBin::Name(Cow::Borrowed(bin.name)),
+        data: OperationData::Value(Cow::Borrowed(&bin.value)),
     }
 }
 
@@ -93,7 +95,7 @@ pub const fn add<'a>(bin: &'a Bin) -> Operation<'a> {
 pub const fn touch<'a>() -> Operation<'a> {
     Operation {
         op: OperationType::Touch,
-        ctx: DEFAULT_CTX,
+        ctx: Cow::Borrowed(DEFAULT_CTX),
         bin: OperationBin::None,
         data: OperationData::None,
     }
@@ -103,7 +105,7 @@ pub const fn touch<'a>() -> Operation<'a> {
 pub const fn delete<'a>() -> Operation<'a> {
     Operation {
         op: OperationType::Delete,
-        ctx: DEFAULT_CTX,
+        ctx: Cow::Borrowed(DEFAULT_CTX),
         bin: OperationBin::None,
         data: OperationData::None,
     }
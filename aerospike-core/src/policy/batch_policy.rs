@@ -13,6 +13,8 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::time::Duration;
+
 use crate::expressions::FilterExpression;
 use crate::policy::{BasePolicy, Concurrency, PolicyLike};
 
@@ -53,6 +55,11 @@ pub struct BatchPolicy {
 
     /// Defines algorithm used to determine the target node for a command. The replica algorithm only affects single record and batch commands.
     pub replica: Replica,
+
+    /// If set, the keys of a node that has not answered within this delay are sent again to the
+    /// next replica in sequence. The first response for each node is used and the other request
+    /// is cancelled. Hedging has no effect with `Replica::Master`. Default: `None` (no hedging).
+    pub hedge_delay: Option<Duration>,
 }
 
 impl BatchPolicy {
@@ -76,6 +83,7 @@ impl Default for BatchPolicy {
             send_set_name: false,
             filter_expression: None,
             replica: Replica::default(),
+            hedge_delay: None,
         }
     }
 }
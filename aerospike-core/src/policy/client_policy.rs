@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::time::Duration;
 
 use crate::commands::admin_command::AdminCommand;
@@ -28,6 +28,10 @@ pub struct ClientPolicy {
     /// to the server host for the first time.
     pub timeout: Option<Duration>,
 
+    /// Timeout when opening a new connection to a server node. `None` waits until the operating
+    /// system gives up on the connection attempt. Default: 5 seconds.
+    pub connect_timeout: Option<Duration>,
+
     /// Connection idle timeout. Every time a connection is used, its idle
     /// deadline will be extended by this duration. When this deadline is reached,
     /// the connection will be closed and discarded from the connection pool.
@@ -36,12 +40,39 @@ pub struct ClientPolicy {
     /// Maximum number of synchronous connections allowed per server node.
     pub max_conns_per_node: usize,
 
+    /// Minimum number of connections allowed per server node. The connections are opened when a
+    /// node is added to the cluster and the pool is topped up to this size on every cluster tend.
+    /// Idle connections are only closed while the pool holds more than this number of
+    /// connections. Must not exceed `max_conns_per_node`. Default: 0.
+    pub min_conns_per_node: usize,
+
+    /// Maximum time to wait for a free connection when all `max_conns_per_node` connections to a
+    /// node are in use. Waiting commands are served in the order they arrived, across all
+    /// connection pools of the node. The wait is also bounded by the command's own deadline. If
+    /// no connection becomes available in time, the command fails with
+    /// `ErrorKind::ConnectionWaitTimeout`. `None` fails immediately with
+    /// `ErrorKind::NoMoreConnections`. Default: `None`.
+    pub conn_wait_timeout: Option<Duration>,
+
     /// Number of connection pools used for each node. Machines with 8 CPU cores or less usually
     /// need only one connection pool per node. Machines with larger number of CPU cores may have
     /// their performance limited by contention for pooled connections. Contention for pooled
     /// connections can be reduced by creating multiple mini connection pools per node.
     pub conn_pools_per_node: usize,
 
+    /// Maximum number of errors allowed per node per `error_rate_window` before the client stops
+    /// sending commands to that node. Errors are network errors, timeouts and device overload
+    /// responses. Commands for a node that exceeded the limit fail fast with
+    /// `ErrorKind::MaxErrorRate`, or are sent to another replica where the replica policy allows.
+    /// Set to 0 to disable. Default: 100.
+    pub max_error_rate: usize,
+
+    /// Number of cluster tend iterations that make up the window in which `max_error_rate` is
+    /// enforced. The error count of every node is reset at the end of each window, so a node
+    /// that exceeded the limit is used again after at most `error_rate_window * tend_interval`.
+    /// Default: 1.
+    pub error_rate_window: usize,
+
     /// Throw exception if host connection fails during addHost().
     pub fail_if_not_connected: bool,
 
@@ -84,14 +115,18 @@ pub struct ClientPolicy {
     /// that support the "cluster-name" info command.
     pub cluster_name: Option<String>,
 
-    /// Mark this client as belonging to a rack, and track server rack data.  This field is useful when directing read commands to 
-	/// the server node that contains the key and exists on the same rack as the client.
+    /// Mark this client as belonging to a rack, and track server rack data.  This field is useful when directing read commands to
+    /// the server node that contains the key and exists on the same rack as the client.
     /// This serves to lower cloud provider costs when nodes are distributed across different
-	/// racks/data centers.
-	///
-	/// Replica.PreferRack and server rack configuration must
-	/// also be set to enable this functionality.
-    pub rack_ids: Option<HashSet<usize>>,
+    /// racks/data centers.
+    ///
+    /// Replica.PreferRack and server rack configuration must
+    /// also be set to enable this functionality.
+    ///
+    /// The racks are preferred in the order given: `Replica::PreferRack` uses a replica on the
+    /// first rack if there is one, then a replica on the second rack, and so on, followed by the
+    /// remaining replicas in sequence.
+    pub rack_ids: Option<Vec<usize>>,
 }
 
 impl Default for ClientPolicy {
@@ -99,9 +134,14 @@ impl Default for ClientPolicy {
         ClientPolicy {
             user_password: None,
             timeout: Some(Duration::new(30, 0)),
+            connect_timeout: Some(Duration::new(5, 0)),
             idle_timeout: Some(Duration::new(5, 0)),
             max_conns_per_node: 256,
+            min_conns_per_node: 0,
+            conn_wait_timeout: None,
             conn_pools_per_node: 1,
+            max_error_rate: 100,
+            error_rate_window: 1,
             fail_if_not_connected: true,
             tend_interval: Duration::new(1, 0),
             ip_map: None,
@@ -13,8 +13,12 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::time::SystemTime;
 use std::u32;
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeZone};
+
 const NAMESPACE_DEFAULT: u32 = 0x0000_0000;
 const NEVER_EXPIRE: u32 = 0xFFFF_FFFF; // -1 as i32
 const DONT_UPDATE: u32 = 0xFFFF_FFFE; // -2 as i32
@@ -35,6 +39,11 @@ pub enum Expiration {
     /// Do not change the record's expiry time when updating the record; requires Aerospike server
     /// version 3.10.1 or later.
     DontUpdate,
+
+    /// Set the record to expire at the given time. The time is converted into the number of
+    /// seconds from now, rounded up, when the command is sent; a time in the past sets the record
+    /// to expire after one second.
+    At(SystemTime),
 }
 
 impl From<Expiration> for u32 {
@@ -44,6 +53,52 @@ impl From<Expiration> for u32 {
             Expiration::NamespaceDefault => NAMESPACE_DEFAULT,
             Expiration::Never => NEVER_EXPIRE,
             Expiration::DontUpdate => DONT_UPDATE,
+            Expiration::At(time) => seconds_until(time),
         }
     }
 }
+
+// Returns the number of seconds from now until `time`, rounded up, but at least one second so
+// that the value is not mistaken for `NAMESPACE_DEFAULT`.
+fn seconds_until(time: SystemTime) -> u32 {
+    let secs = match time.duration_since(SystemTime::now()) {
+        Ok(d) if d.subsec_nanos() > 0 => d.as_secs() + 1,
+        Ok(d) => d.as_secs(),
+        Err(_) => 1,
+    };
+    secs.clamp(1, u64::from(DONT_UPDATE - 1)) as u32
+}
+
+impl From<SystemTime> for Expiration {
+    fn from(time: SystemTime) -> Expiration {
+        Expiration::At(time)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: TimeZone> From<DateTime<Tz>> for Expiration {
+    fn from(time: DateTime<Tz>) -> Expiration {
+        Expiration::At(time.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::Expiration;
+
+    #[test]
+    fn expire_at() {
+        let now = SystemTime::now();
+        assert_eq!(
+            u32::from(Expiration::At(now + Duration::from_secs(3600))),
+            3600
+        );
+        assert_eq!(
+            u32::from(Expiration::At(now + Duration::from_millis(1500))),
+            2
+        );
+        assert_eq!(u32::from(Expiration::At(now - Duration::from_secs(10))), 1);
+    }
+}
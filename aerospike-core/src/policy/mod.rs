@@ -29,6 +29,7 @@ mod query_policy;
 mod read_policy;
 mod record_exists_action;
 mod scan_policy;
+mod update_policy;
 mod write_policy;
 
 pub use self::admin_policy::AdminPolicy;
@@ -44,9 +45,11 @@ pub use self::query_policy::QueryPolicy;
 pub use self::read_policy::ReadPolicy;
 pub use self::record_exists_action::RecordExistsAction;
 pub use self::scan_policy::ScanPolicy;
+pub use self::update_policy::UpdatePolicy;
 pub use self::write_policy::WritePolicy;
 
 use crate::expressions::FilterExpression;
+use crate::Txn;
 use aerospike_rt::time::{Duration, Instant};
 use std::option::Option;
 
@@ -117,16 +120,36 @@ where
     }
 }
 
-
-/// Defines algorithm used to determine the target node for a command. The replica algorithm only affects single record and batch commands.
+/// Defines algorithm used to determine the target node for a command. The replica algorithm
+/// affects single record and batch commands, and the distribution of partitions for scans and
+/// queries.
 #[derive(Debug, Copy, Clone)]
 pub enum Replica {
     /// Use node containing key's master partition.
     Master,
     /// Try node containing master partition first. If connection fails, all commands try nodes containing replicated partitions. If socketTimeout is reached, reads also try nodes containing replicated partitions, but writes remain on master node.
     Sequence,
-    /// Try node on the same rack as the client first. If there are no nodes on the same rack, use SEQUENCE instead. 
+    /// Try node on the same rack as the client first. If there are no nodes on the same rack, use SEQUENCE instead.
     PreferRack,
+    /// Distribute commands across the master and all proles (replicas) in round-robin fashion.
+    /// Retries go to the next replica in sequence.
+    MasterProles,
+    /// Distribute commands to a random node among the master and its proles. Retries go to the
+    /// next replica in sequence.
+    Random,
+}
+
+impl Replica {
+    /// Returns the hedge delay to use for this replica algorithm. Hedging requires a sequence of
+    /// nodes to choose from, so it is disabled for `Replica::Master`.
+    pub(crate) const fn hedge_delay(self, hedge_delay: Option<Duration>) -> Option<Duration> {
+        match self {
+            Replica::Master => None,
+            Replica::Sequence | Replica::PreferRack | Replica::MasterProles | Replica::Random => {
+                hedge_delay
+            }
+        }
+    }
 }
 
 impl Default for Replica {
@@ -166,6 +189,11 @@ pub struct BasePolicy {
 
     /// Optional FilterExpression
     pub filter_expression: Option<FilterExpression>,
+
+    /// Multi-record transaction the command takes part in. Only single record commands take
+    /// part in transactions; batch, scan and query commands fail with an invalid argument error
+    /// if it is set. Default: `None`.
+    pub txn: Option<Txn>,
 }
 
 impl Policy for BasePolicy {
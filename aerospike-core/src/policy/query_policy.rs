@@ -14,7 +14,7 @@
 // the License.
 
 use crate::expressions::FilterExpression;
-use crate::policy::{BasePolicy, PolicyLike};
+use crate::policy::{BasePolicy, PolicyLike, Replica};
 
 /// `QueryPolicy` encapsulates parameters for query operations.
 #[derive(Debug, Clone)]
@@ -22,6 +22,10 @@ pub struct QueryPolicy {
     /// Base policy instance
     pub base_policy: BasePolicy,
 
+    /// Defines how the partitions of the namespace are assigned to nodes. By default, each
+    /// partition is queried on the node holding its master copy.
+    pub replica: Replica,
+
     /// Maximum number of concurrent requests to server nodes at any point in time. If there are 16
     /// nodes in the cluster and `max_concurrent_nodes` is 8, then queries will be made to 8 nodes
     /// in parallel. When a query completes, a new query will be issued until all 16 nodes have
@@ -56,6 +60,7 @@ impl Default for QueryPolicy {
     fn default() -> Self {
         QueryPolicy {
             base_policy: BasePolicy::default(),
+            replica: Replica::Master,
             max_concurrent_nodes: 0,
             record_queue_size: 1024,
             fail_on_cluster_change: true,
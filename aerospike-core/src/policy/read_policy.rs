@@ -29,6 +29,12 @@ pub struct ReadPolicy {
 
     /// Defines algorithm used to determine the target node for a command. The replica algorithm only affects single record and batch commands.
     pub replica: Replica,
+
+    /// If set, a duplicate request is sent to the next replica in sequence when the first node has
+    /// not answered within this delay. The first response is returned and the other request is
+    /// cancelled. Hedging has no effect with `Replica::Master`, since there is no other node to
+    /// send the duplicate request to. Default: `None` (no hedging).
+    pub hedge_delay: Option<Duration>,
 }
 
 impl Default for BasePolicy {
@@ -40,6 +46,7 @@ impl Default for BasePolicy {
             sleep_between_retries: Some(Duration::new(0, 500_000_000)),
             consistency_level: ConsistencyLevel::ConsistencyOne,
             filter_expression: None,
+            txn: None,
         }
     }
 }
@@ -14,7 +14,7 @@
 // the License.
 
 use crate::expressions::FilterExpression;
-use crate::policy::{BasePolicy, PolicyLike};
+use crate::policy::{BasePolicy, PolicyLike, Replica};
 
 /// `ScanPolicy` encapsulates optional parameters used in scan operations.
 #[derive(Debug, Clone)]
@@ -22,6 +22,10 @@ pub struct ScanPolicy {
     /// Base policy instance
     pub base_policy: BasePolicy,
 
+    /// Defines how the partitions of the namespace are assigned to nodes. By default, each
+    /// partition is scanned on the node holding its master copy.
+    pub replica: Replica,
+
     /// Percent of data to scan. Valid integer range is 1 to 100. Default is 100.
     /// This is deprected and won't be sent to the server.
     pub scan_percent: u8,
@@ -66,6 +70,7 @@ impl Default for ScanPolicy {
     fn default() -> Self {
         ScanPolicy {
             base_policy: BasePolicy::default(),
+            replica: Replica::Master,
             scan_percent: 100,
             max_concurrent_nodes: 0,
             record_queue_size: 1024,
@@ -0,0 +1,105 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::time::Duration;
+
+use crate::policy::WritePolicy;
+
+/// `UpdatePolicy` encapsulates parameters for read-modify-write cycles using
+/// `Client::update_with`.
+#[derive(Debug, Clone)]
+pub struct UpdatePolicy {
+    /// Write policy used to write the updated record. Its base policy is also used to read the
+    /// record. The generation policy and generation are set by `update_with`, based on the
+    /// generation of the record that was read; if the record does not exist, it is written with
+    /// `RecordExistsAction::CreateOnly`. All other fields are used as given.
+    pub write_policy: WritePolicy,
+
+    /// Maximum number of times the read-modify-write cycle is retried when the record was
+    /// modified, created or deleted concurrently.
+    ///
+    /// Default: 5
+    pub max_retries: usize,
+
+    /// Time to sleep before the first retry. The sleep time is doubled with each subsequent
+    /// retry, up to `max_sleep_between_retries`. Set to `None` to retry immediately.
+    ///
+    /// Default: 10ms
+    pub sleep_between_retries: Option<Duration>,
+
+    /// Upper bound of the time to sleep between retries.
+    ///
+    /// Default: 1s
+    pub max_sleep_between_retries: Duration,
+}
+
+impl UpdatePolicy {
+    /// Create a new update policy instance that uses the given write policy.
+    pub fn new(write_policy: WritePolicy) -> Self {
+        UpdatePolicy {
+            write_policy,
+            ..UpdatePolicy::default()
+        }
+    }
+
+    // Returns the time to sleep before the given retry, starting at 1.
+    pub(crate) fn backoff(&self, retry: usize) -> Option<Duration> {
+        let sleep = self.sleep_between_retries?;
+        let factor = 1u32
+            .checked_shl(retry.saturating_sub(1) as u32)
+            .unwrap_or(u32::MAX);
+        Some(
+            sleep
+                .checked_mul(factor)
+                .map_or(self.max_sleep_between_retries, |sleep| {
+                    sleep.min(self.max_sleep_between_retries)
+                }),
+        )
+    }
+}
+
+impl Default for UpdatePolicy {
+    fn default() -> Self {
+        UpdatePolicy {
+            write_policy: WritePolicy::default(),
+            max_retries: 5,
+            sleep_between_retries: Some(Duration::from_millis(10)),
+            max_sleep_between_retries: Duration::from_secs(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::UpdatePolicy;
+
+    #[test]
+    fn backoff() {
+        let policy = UpdatePolicy::default();
+        assert_eq!(policy.backoff(1), Some(Duration::from_millis(10)));
+        assert_eq!(policy.backoff(2), Some(Duration::from_millis(20)));
+        assert_eq!(policy.backoff(4), Some(Duration::from_millis(80)));
+        assert_eq!(policy.backoff(8), Some(Duration::from_secs(1)));
+        assert_eq!(policy.backoff(100), Some(Duration::from_secs(1)));
+
+        let policy = UpdatePolicy {
+            sleep_between_retries: None,
+            ..UpdatePolicy::default()
+        };
+        assert_eq!(policy.backoff(1), None);
+    }
+}
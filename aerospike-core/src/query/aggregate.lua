@@ -0,0 +1,250 @@
+-- Client-side runtime for stream UDF aggregations. Provides the `list` and `map` types, the
+-- logging functions and the stream operations of the server's `aerospike.lua` and
+-- `stream_ops.lua`, so that the final reduce stage of an aggregation can be run locally.
+
+local ListMeta = {}
+local MapMeta = {}
+
+-- list
+
+list = setmetatable({}, {
+    __call = function(_, values)
+        local l = { n = 0 }
+        for i, v in ipairs(values or {}) do
+            l[i] = v
+            l.n = i
+        end
+        return setmetatable(l, ListMeta)
+    end
+})
+
+ListMeta.__index = function(l, i)
+    if type(i) == "number" then
+        return rawget(l, i)
+    end
+    return list[i]
+end
+ListMeta.__len = function(l) return l.n end
+ListMeta.__newindex = function(l, i, v)
+    rawset(l, i, v)
+    if type(i) == "number" and i > l.n then l.n = i end
+end
+
+function list.size(l) return l.n end
+
+function list.append(l, v)
+    l[l.n + 1] = v
+end
+
+function list.prepend(l, v)
+    for i = l.n, 1, -1 do rawset(l, i + 1, rawget(l, i)) end
+    rawset(l, 1, v)
+    l.n = l.n + 1
+end
+
+function list.insert(l, pos, v)
+    for i = l.n, pos, -1 do rawset(l, i + 1, rawget(l, i)) end
+    rawset(l, pos, v)
+    l.n = math.max(l.n + 1, pos)
+end
+
+function list.remove(l, pos)
+    if pos < 1 or pos > l.n then return end
+    for i = pos, l.n - 1 do rawset(l, i, rawget(l, i + 1)) end
+    rawset(l, l.n, nil)
+    l.n = l.n - 1
+end
+
+function list.take(l, n)
+    local r = list()
+    for i = 1, math.min(n, l.n) do list.append(r, rawget(l, i)) end
+    return r
+end
+
+function list.drop(l, n)
+    local r = list()
+    for i = n + 1, l.n do list.append(r, rawget(l, i)) end
+    return r
+end
+
+function list.clone(l)
+    return list.drop(l, 0)
+end
+
+function list.concat(l, other)
+    for i = 1, other.n do list.append(l, rawget(other, i)) end
+end
+
+function list.merge(l, other)
+    local r = list.clone(l)
+    list.concat(r, other)
+    return r
+end
+
+function list.iterator(l)
+    local i = 0
+    return function()
+        i = i + 1
+        if i <= l.n then return rawget(l, i) end
+    end
+end
+
+-- map
+
+map = setmetatable({}, {
+    __call = function(_, values)
+        local m = {}
+        for k, v in pairs(values or {}) do m[k] = v end
+        return setmetatable(m, MapMeta)
+    end
+})
+
+MapMeta.__len = function(m) return map.size(m) end
+
+function map.size(m)
+    local size = 0
+    for _ in next, m do size = size + 1 end
+    return size
+end
+
+function map.remove(m, k)
+    m[k] = nil
+end
+
+function map.pairs(m)
+    local k, v
+    return function()
+        k, v = next(m, k)
+        return k, v
+    end
+end
+
+function map.keys(m)
+    local k
+    return function()
+        k = next(m, k)
+        return k
+    end
+end
+
+function map.values(m)
+    local k, v
+    return function()
+        k, v = next(m, k)
+        return v
+    end
+end
+
+function map.clone(m)
+    return map(m)
+end
+
+function map.merge(m, other, f)
+    local r = map(m)
+    for k, v in next, other do
+        if f ~= nil and r[k] ~= nil then
+            r[k] = f(r[k], v)
+        else
+            r[k] = v
+        end
+    end
+    return r
+end
+
+function map.diff(m, other)
+    local r = map()
+    for k, v in next, m do
+        if other[k] == nil then r[k] = v end
+    end
+    for k, v in next, other do
+        if m[k] == nil then r[k] = v end
+    end
+    return r
+end
+
+function __is_list(v) return getmetatable(v) == ListMeta end
+function __is_map(v) return getmetatable(v) == MapMeta end
+
+-- logging
+
+function trace(fmt, ...) __log(4, string.format(fmt, ...)) end
+function debug(fmt, ...) __log(3, string.format(fmt, ...)) end
+function info(fmt, ...) __log(2, string.format(fmt, ...)) end
+function warn(fmt, ...) __log(1, string.format(fmt, ...)) end
+
+-- stream operations
+
+local SCOPE_SERVER = 1
+local SCOPE_CLIENT = 2
+local SCOPE_EITHER = 3
+local SCOPE_BOTH = 4
+
+local StreamOps = {}
+StreamOps.__index = StreamOps
+
+function StreamOps:filter(f)
+    table.insert(self.ops, { scope = SCOPE_EITHER, apply = function(values)
+        local out = {}
+        for _, v in ipairs(values) do
+            if f(v) then table.insert(out, v) end
+        end
+        return out
+    end })
+    return self
+end
+
+function StreamOps:map(f)
+    table.insert(self.ops, { scope = SCOPE_EITHER, apply = function(values)
+        local out = {}
+        for _, v in ipairs(values) do
+            local r = f(v)
+            if r ~= nil then table.insert(out, r) end
+        end
+        return out
+    end })
+    return self
+end
+
+function StreamOps:aggregate(init, f)
+    table.insert(self.ops, { scope = SCOPE_SERVER, apply = function(values)
+        local acc = init
+        for _, v in ipairs(values) do acc = f(acc, v) end
+        return { acc }
+    end })
+    return self
+end
+
+function StreamOps:reduce(f)
+    table.insert(self.ops, { scope = SCOPE_BOTH, apply = function(values)
+        local acc = values[1]
+        if acc == nil then return {} end
+        for i = 2, #values do acc = f(acc, values[i]) end
+        return { acc }
+    end })
+    return self
+end
+
+-- Returns the operations to run on the client: all operations starting with the first one that
+-- runs on both the server and the client.
+local function client_ops(ops)
+    local selected = {}
+    local client = false
+    for _, op in ipairs(ops) do
+        if op.scope == SCOPE_BOTH or op.scope == SCOPE_CLIENT then client = true end
+        if client then table.insert(selected, op) end
+    end
+    return selected
+end
+
+-- Applies the client side operations of the stream UDF `f` to the values returned by the
+-- server nodes.
+function __apply_stream(f, values, ...)
+    local stream = f(setmetatable({ ops = {} }, StreamOps), ...)
+    if getmetatable(stream) ~= StreamOps then
+        error("aggregation function did not return a stream")
+    end
+    for _, op in ipairs(client_ops(stream.ops)) do
+        values = op.apply(values)
+    end
+    return values
+end
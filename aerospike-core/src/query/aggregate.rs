@@ -0,0 +1,333 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use mlua::{
+    Function, Lua, MetaMethod, MultiValue, Table, TableExt, UserData, UserDataMethods,
+    Value as LuaValue,
+};
+
+use crate::errors::{ErrorKind, Result};
+use crate::query::statement::Aggregation;
+use crate::value::FloatValue;
+use crate::Value;
+
+// Lua implementation of the `list` and `map` types and the stream operations available to stream
+// UDFs on the server.
+const RUNTIME: &str = include_str!("aggregate.lua");
+
+// Runs the client side stages of a stream UDF aggregation, i.e. the first `reduce` and all
+// following operations of the stream, on the partial results returned by the server nodes.
+pub fn apply_stream(
+    udf_body: &[u8],
+    aggregation: &Aggregation,
+    values: &[Value],
+) -> Result<Vec<Value>> {
+    let lua = Lua::new();
+    run(&lua, udf_body, aggregation, values).map_err(|err| {
+        ErrorKind::UdfBadResponse(format!(
+            "Aggregation {}.{} failed: {}",
+            aggregation.package_name, aggregation.function_name, err
+        ))
+        .into()
+    })
+}
+
+fn run(
+    lua: &Lua,
+    udf_body: &[u8],
+    aggregation: &Aggregation,
+    values: &[Value],
+) -> mlua::Result<Vec<Value>> {
+    let globals = lua.globals();
+    let log = lua.create_function(|_, (level, message): (u8, String)| {
+        match level {
+            1 => warn!("{}", message),
+            2 => info!("{}", message),
+            3 => debug!("{}", message),
+            _ => trace!("{}", message),
+        }
+        Ok(())
+    })?;
+    globals.set("__log", log)?;
+    lua.load(RUNTIME).set_name("aggregate.lua").exec()?;
+    lua.load(udf_body)
+        .set_name(aggregation.package_name.as_str())
+        .exec()?;
+
+    let types = LuaTypes {
+        list: globals.get("list")?,
+        map: globals.get("map")?,
+        is_list: globals.get("__is_list")?,
+        is_map: globals.get("__is_map")?,
+    };
+
+    let function: Function = globals.get(aggregation.function_name.as_str())?;
+    let input = values
+        .iter()
+        .map(|value| types.to_lua(lua, value))
+        .collect::<mlua::Result<Vec<_>>>()?;
+    let input = lua.create_sequence_from(input)?;
+    let mut args = vec![LuaValue::Function(function), LuaValue::Table(input)];
+    for arg in aggregation.function_args.iter().flatten() {
+        args.push(types.to_lua(lua, arg)?);
+    }
+
+    let apply_stream: Function = globals.get("__apply_stream")?;
+    let output: Table = apply_stream.call(MultiValue::from_vec(args))?;
+    output
+        .sequence_values::<LuaValue>()
+        .map(|value| types.to_value(value?))
+        .collect()
+}
+
+// Blob and HLL values are passed to Lua as userdata, so they are not mistaken for strings when
+// they are converted back.
+struct LuaBytes(Value);
+
+impl UserData for LuaBytes {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::Len, |_, this, ()| match this.0 {
+            Value::Blob(ref val) | Value::HLL(ref val) => Ok(val.len()),
+            _ => Ok(0),
+        });
+        methods.add_meta_method(MetaMethod::Eq, |_, this, other: mlua::AnyUserData| {
+            Ok(other
+                .borrow::<LuaBytes>()
+                .map_or(false, |other| other.0 == this.0))
+        });
+    }
+}
+
+// Constructors and type checks for the `list` and `map` types of the Lua runtime.
+struct LuaTypes<'lua> {
+    list: Table<'lua>,
+    map: Table<'lua>,
+    is_list: Function<'lua>,
+    is_map: Function<'lua>,
+}
+
+impl<'lua> LuaTypes<'lua> {
+    fn to_lua(&self, lua: &'lua Lua, value: &Value) -> mlua::Result<LuaValue<'lua>> {
+        let value = match *value {
+            Value::Nil => LuaValue::Nil,
+            Value::Bool(val) => LuaValue::Boolean(val),
+            Value::Int(val) => LuaValue::Integer(val),
+            Value::UInt(val) => match i64::try_from(val) {
+                Ok(val) => LuaValue::Integer(val),
+                Err(_) => LuaValue::Number(val as f64),
+            },
+            Value::Float(FloatValue::F64(val)) => LuaValue::Number(f64::from_bits(val)),
+            Value::Float(FloatValue::F32(val)) => LuaValue::Number(f32::from_bits(val).into()),
+            Value::String(ref val) | Value::GeoJSON(ref val) => {
+                LuaValue::String(lua.create_string(val)?)
+            }
+            Value::Blob(_) | Value::HLL(_) => {
+                LuaValue::UserData(lua.create_userdata(LuaBytes(value.clone()))?)
+            }
+            Value::List(ref values) => {
+                let list: Table = self.list.call(())?;
+                for (i, value) in values.iter().enumerate() {
+                    list.raw_set(i + 1, self.to_lua(lua, value)?)?;
+                }
+                list.raw_set("n", values.len())?;
+                LuaValue::Table(list)
+            }
+            Value::HashMap(ref values) => {
+                let map: Table = self.map.call(())?;
+                for (key, value) in values {
+                    map.raw_set(self.to_lua(lua, key)?, self.to_lua(lua, value)?)?;
+                }
+                LuaValue::Table(map)
+            }
+            Value::OrderedMap(ref values) => {
+                let map: Table = self.map.call(())?;
+                for (key, value) in values {
+                    map.raw_set(self.to_lua(lua, key)?, self.to_lua(lua, value)?)?;
+                }
+                LuaValue::Table(map)
+            }
+        };
+        Ok(value)
+    }
+
+    fn to_value(&self, value: LuaValue<'lua>) -> mlua::Result<Value> {
+        let value = match value {
+            LuaValue::Nil => Value::Nil,
+            LuaValue::Boolean(val) => Value::Bool(val),
+            LuaValue::Integer(val) => Value::Int(val),
+            // Lua has no separate integer type for the results of e.g. divisions; like the
+            // server, return integral numbers as integers.
+            LuaValue::Number(val)
+                if val.fract() == 0.0 && val >= i64::MIN as f64 && val < i64::MAX as f64 =>
+            {
+                Value::Int(val as i64)
+            }
+            LuaValue::Number(val) => Value::Float(FloatValue::from(val)),
+            LuaValue::String(val) => match val.to_str() {
+                Ok(val) => Value::String(val.to_string()),
+                Err(_) => Value::Blob(val.as_bytes().to_vec()),
+            },
+            LuaValue::UserData(ref val) if val.is::<LuaBytes>() => {
+                val.borrow::<LuaBytes>()?.0.clone()
+            }
+            LuaValue::Table(table) => {
+                if self.is_list.call::<_, bool>(table.clone())? {
+                    let len: usize = table.raw_get("n")?;
+                    let mut values = Vec::with_capacity(len);
+                    for i in 1..=len {
+                        values.push(self.to_value(table.raw_get(i)?)?);
+                    }
+                    Value::List(values)
+                } else if self.is_map.call::<_, bool>(table.clone())? || table.raw_len() == 0 {
+                    let mut values = HashMap::new();
+                    for pair in table.pairs::<LuaValue, LuaValue>() {
+                        let (key, value) = pair?;
+                        values.insert(self.to_value(key)?, self.to_value(value)?);
+                    }
+                    Value::HashMap(values)
+                } else {
+                    let values = table
+                        .sequence_values::<LuaValue>()
+                        .map(|value| self.to_value(value?))
+                        .collect::<mlua::Result<_>>()?;
+                    Value::List(values)
+                }
+            }
+            value => {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "Unsupported aggregation result type: {}",
+                    value.type_name()
+                )))
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply_stream;
+    use crate::query::statement::Aggregation;
+    use crate::Value;
+
+    const UDF: &str = r#"
+local function add(a, b)
+  return a + b
+end
+
+function sum(stream)
+  return stream : map(function(rec) return rec['bin'] end) : reduce(add)
+end
+
+function sum_above(stream, min)
+  return stream : filter(function(rec) return rec['bin'] > min end) : reduce(add)
+end
+
+function count_by_parity(stream)
+  local function merge(a, b)
+    return map.merge(a, b, add)
+  end
+  return stream : aggregate(map(), function(m, rec) return m end) : reduce(merge)
+    : map(function(m) return list{m['even'], m['odd']} end)
+end
+
+function divide(stream)
+  return stream : reduce(add) : map(function(sum) return list{sum / 2, sum / 4} end)
+end
+
+function no_reduce(stream)
+  return stream : map(function(rec) return rec['bin'] end)
+end
+"#;
+
+    fn aggregation(function_name: &str, args: Option<Vec<Value>>) -> Aggregation {
+        Aggregation {
+            package_name: "test".to_string(),
+            function_name: function_name.to_string(),
+            function_args: args,
+        }
+    }
+
+    #[test]
+    fn reduce() {
+        // partial sums returned by three nodes
+        let values = vec![Value::from(10), Value::from(20), Value::from(12)];
+        let result = apply_stream(UDF.as_bytes(), &aggregation("sum", None), &values).unwrap();
+        assert_eq!(result, vec![Value::from(42)]);
+
+        // the filter ran on the server; the client only reduces
+        let args = Some(vec![Value::from(100)]);
+        let result =
+            apply_stream(UDF.as_bytes(), &aggregation("sum_above", args), &values).unwrap();
+        assert_eq!(result, vec![Value::from(42)]);
+
+        let result = apply_stream(UDF.as_bytes(), &aggregation("sum", None), &[]).unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn reduce_maps() {
+        let values = vec![
+            as_map!("even" => 2, "odd" => 3),
+            as_map!("even" => 4, "odd" => 1),
+        ];
+        let result = apply_stream(
+            UDF.as_bytes(),
+            &aggregation("count_by_parity", None),
+            &values,
+        )
+        .unwrap();
+        assert_eq!(result, vec![as_list!(6, 4)]);
+    }
+
+    #[test]
+    fn no_client_stage() {
+        let values = vec![Value::from(1), Value::from("two")];
+        let result =
+            apply_stream(UDF.as_bytes(), &aggregation("no_reduce", None), &values).unwrap();
+        assert_eq!(result, values);
+    }
+
+    #[test]
+    fn numbers() {
+        let values = vec![Value::from(3), Value::from(5)];
+        let result = apply_stream(UDF.as_bytes(), &aggregation("divide", None), &values).unwrap();
+        assert_eq!(result, vec![as_list!(4, 2)]);
+
+        let values = vec![Value::from(3), Value::from(4)];
+        let result = apply_stream(UDF.as_bytes(), &aggregation("divide", None), &values).unwrap();
+        assert_eq!(result, vec![as_list!(3.5, 1.75)]);
+    }
+
+    #[test]
+    fn blobs() {
+        let values = vec![Value::Blob(b"text".to_vec()), Value::HLL(vec![0, 1, 2])];
+        let result =
+            apply_stream(UDF.as_bytes(), &aggregation("no_reduce", None), &values).unwrap();
+        assert_eq!(result, values);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(apply_stream(UDF.as_bytes(), &aggregation("missing", None), &[]).is_err());
+        assert!(apply_stream(b"function sum(", &aggregation("sum", None), &[]).is_err());
+
+        let values = vec![Value::from(1), Value::from("two")];
+        assert!(apply_stream(UDF.as_bytes(), &aggregation("sum", None), &values).is_err());
+    }
+}
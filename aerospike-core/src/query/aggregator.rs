@@ -0,0 +1,269 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crate::errors::Result;
+use crate::query::Recordset;
+use crate::Record;
+
+type Init<A> = Arc<dyn Fn() -> A + Send + Sync>;
+type Fold<A> = Arc<dyn Fn(A, Record) -> A + Send + Sync>;
+type Combine<A> = Arc<dyn Fn(A, A) -> A + Send + Sync>;
+type Transform = Arc<dyn Fn(Record) -> Option<Record> + Send + Sync>;
+
+/// Client-side aggregation of the records returned by a scan or query, used with
+/// `Client::scan_fold` and `Client::query_fold`.
+///
+/// The records of each server node are folded into a separate partial result as they arrive, so
+/// that no more than the policy's `record_queue_size` records per node are buffered at any time.
+/// Once all nodes are done, the partial results are merged using the `combine` function.
+///
+/// Records can be filtered and transformed before they are folded; these stages are applied in
+/// the order in which they were added.
+///
+/// # Examples
+///
+/// Sum up the values of bin "amount" per value of bin "category".
+///
+/// ```rust
+/// # use aerospike::*;
+///
+/// let sum = Aggregator::new(
+///     || 0,
+///     |sum, record| match record.bins.get("amount") {
+///         Some(Value::Int(amount)) => sum + amount,
+///         _ => sum,
+///     },
+///     |a, b| a + b,
+/// );
+/// let sum_by_category = sum
+///     .filter(|record| record.bins.contains_key("category"))
+///     .group_by(|record| record.bins["category"].clone());
+/// ```
+pub struct Aggregator<A> {
+    init: Init<A>,
+    fold: Fold<A>,
+    combine: Combine<A>,
+    transform: Transform,
+}
+
+impl<A> Clone for Aggregator<A> {
+    fn clone(&self) -> Self {
+        Aggregator {
+            init: self.init.clone(),
+            fold: self.fold.clone(),
+            combine: self.combine.clone(),
+            transform: self.transform.clone(),
+        }
+    }
+}
+
+impl<A: 'static> Aggregator<A> {
+    /// Create a new aggregator. `init` creates the initial partial result of each node, `fold`
+    /// adds a record to a partial result and `combine` merges two partial results.
+    pub fn new<I, F, C>(init: I, fold: F, combine: C) -> Self
+    where
+        I: Fn() -> A + Send + Sync + 'static,
+        F: Fn(A, Record) -> A + Send + Sync + 'static,
+        C: Fn(A, A) -> A + Send + Sync + 'static,
+    {
+        Aggregator {
+            init: Arc::new(init),
+            fold: Arc::new(fold),
+            combine: Arc::new(combine),
+            transform: Arc::new(Some),
+        }
+    }
+
+    /// Only aggregate the records matching the predicate.
+    #[must_use]
+    pub fn filter<P>(self, predicate: P) -> Self
+    where
+        P: Fn(&Record) -> bool + Send + Sync + 'static,
+    {
+        let transform = self.transform;
+        Aggregator {
+            transform: Arc::new(move |record| transform(record).filter(|record| predicate(record))),
+            ..self
+        }
+    }
+
+    /// Transform each record before it is aggregated.
+    #[must_use]
+    pub fn map<M>(self, mapper: M) -> Self
+    where
+        M: Fn(Record) -> Record + Send + Sync + 'static,
+    {
+        let transform = self.transform;
+        Aggregator {
+            transform: Arc::new(move |record| transform(record).map(&mapper)),
+            ..self
+        }
+    }
+
+    /// Aggregate the records separately for each key returned by `key`.
+    pub fn group_by<K, G>(self, key: G) -> Aggregator<HashMap<K, A>>
+    where
+        K: Eq + Hash + 'static,
+        G: Fn(&Record) -> K + Send + Sync + 'static,
+    {
+        let Aggregator {
+            init,
+            fold,
+            combine,
+            transform,
+        } = self;
+        let grouped = Aggregator::new(
+            HashMap::new,
+            move |mut groups: HashMap<K, A>, record| {
+                let key = key(&record);
+                let acc = groups.remove(&key).unwrap_or_else(|| init());
+                groups.insert(key, fold(acc, record));
+                groups
+            },
+            move |mut groups, other| {
+                for (key, acc) in other {
+                    let merged = match groups.remove(&key) {
+                        Some(group) => combine(group, acc),
+                        None => acc,
+                    };
+                    groups.insert(key, merged);
+                }
+                groups
+            },
+        );
+        Aggregator {
+            transform,
+            ..grouped
+        }
+    }
+
+    // Folds the records of a single node's recordset, as they arrive, into a partial result.
+    // Closes the recordset on error, so that the node's command stops reading records.
+    pub(crate) async fn fold_recordset(&self, recordset: &Recordset) -> Result<A> {
+        let mut acc = (self.init)();
+        while let Some(record) = recordset.next_record().await {
+            match record {
+                Ok(record) => {
+                    if let Some(record) = (self.transform)(record) {
+                        acc = (self.fold)(acc, record);
+                    }
+                }
+                Err(err) => {
+                    recordset.close();
+                    return Err(err);
+                }
+            }
+        }
+        Ok(acc)
+    }
+
+    // Merges the partial results of all nodes.
+    pub(crate) fn combine(&self, partials: Vec<A>) -> A {
+        partials
+            .into_iter()
+            .reduce(|a, b| (self.combine)(a, b))
+            .unwrap_or_else(|| (self.init)())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Aggregator;
+    use crate::{Record, Recordset, Value};
+
+    fn record(category: &str, amount: i64) -> Record {
+        let mut bins = HashMap::new();
+        bins.insert("category".to_string(), Value::from(category));
+        bins.insert("amount".to_string(), Value::from(amount));
+        Record::new(None, bins, 0, 0)
+    }
+
+    fn sum() -> Aggregator<i64> {
+        Aggregator::new(
+            || 0,
+            |sum, record| match record.bins.get("amount") {
+                Some(Value::Int(amount)) => sum + amount,
+                _ => sum,
+            },
+            |a, b| a + b,
+        )
+    }
+
+    fn fold<A: 'static>(aggregator: &Aggregator<A>, records: Vec<Record>) -> A {
+        let recordset = Recordset::new(records.len() + 1, 1);
+        for record in records {
+            assert!(recordset.push(Ok(record)).is_none());
+        }
+        recordset.signal_end();
+        futures::executor::block_on(aggregator.fold_recordset(&recordset)).unwrap()
+    }
+
+    #[test]
+    fn fold_and_combine() {
+        let aggregator = sum();
+        let a = fold(&aggregator, vec![record("a", 1), record("b", 2)]);
+        let b = fold(&aggregator, vec![record("a", 3)]);
+        assert_eq!(aggregator.combine(vec![a, b]), 6);
+        assert_eq!(aggregator.combine(vec![]), 0);
+
+        // stages apply in order
+        let aggregator = sum()
+            .filter(|record| record.bins["category"] == Value::from("a"))
+            .map(|mut record| {
+                record.bins.insert("category".to_string(), Value::from("b"));
+                record.bins.insert("amount".to_string(), Value::from(10));
+                record
+            });
+        let a = fold(
+            &aggregator,
+            vec![record("a", 1), record("b", 2), record("a", 3)],
+        );
+        assert_eq!(aggregator.combine(vec![a]), 20);
+    }
+
+    #[test]
+    fn node_error() {
+        // the error of a failed node reaches the fold even if the queue is full
+        let recordset = Recordset::new(1, 1);
+        assert!(recordset.push(Ok(record("a", 1))).is_none());
+        let failed_node = async {
+            recordset.push_error("Node failed".into()).await;
+            recordset.signal_end();
+        };
+        let ((), res) = futures::executor::block_on(futures::future::join(
+            failed_node,
+            sum().fold_recordset(&recordset),
+        ));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn group_by() {
+        let aggregator = sum().group_by(|record| record.bins["category"].clone());
+        let a = fold(&aggregator, vec![record("a", 1), record("b", 2)]);
+        let b = fold(&aggregator, vec![record("a", 3), record("c", 4)]);
+        let groups = aggregator.combine(vec![a, b]);
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&Value::from("a")], 4);
+        assert_eq!(groups[&Value::from("b")], 2);
+        assert_eq!(groups[&Value::from("c")], 4);
+    }
+}
@@ -14,6 +14,8 @@
 // the License.
 
 use crate::commands::{buffer::Buffer, ParticleType};
+use crate::msgpack::encoder;
+use crate::operations::cdt_context::CdtContext;
 use crate::{CollectionIndexType, Value};
 
 /// Query filter definition. Currently, only one filter is allowed in a Statement, and must be on a
@@ -40,6 +42,8 @@ pub struct Filter {
 
     #[doc(hidden)]
     pub end: Value,
+
+    ctx: Vec<CdtContext>,
 }
 
 impl Filter {
@@ -59,9 +63,36 @@ impl Filter {
             value_particle_type,
             begin,
             end,
+            ctx: vec![],
         }
     }
 
+    /// Applies the filter to the nested list or map element identified by the context path,
+    /// instead of the bin itself. The secondary index must have been created with the same
+    /// context.
+    pub fn set_context(mut self, ctx: &[CdtContext]) -> Self {
+        self.ctx = ctx.to_vec();
+        self
+    }
+
+    /// Returns the context path of the filter.
+    pub fn context(&self) -> &[CdtContext] {
+        &self.ctx
+    }
+
+    #[doc(hidden)]
+    pub fn estimate_context_size(&self) -> usize {
+        if self.ctx.is_empty() {
+            return 0;
+        }
+        encoder::pack_cdt_context(&mut None, &self.ctx)
+    }
+
+    #[doc(hidden)]
+    pub fn write_context(&self, buffer: &mut Buffer) {
+        encoder::pack_cdt_context(&mut Some(buffer), &self.ctx);
+    }
+
     #[doc(hidden)]
     pub fn collection_index_type(&self) -> CollectionIndexType {
         self.collection_index_type.clone()
@@ -144,13 +175,14 @@ macro_rules! as_contains_range {
     }};
 }
 
-/// Create geospatial "points within region" filter for queries. For queries on a collection index
-/// the collection index type must be specified.
+/// Create geospatial "points within region" filter for queries. The region can be given as a
+/// GeoJSON string or as one of the typed geospatial values, e.g. a `GeoPolygon` or `GeoCircle`.
+/// For queries on a collection index the collection index type must be specified.
 #[macro_export]
 macro_rules! as_within_region {
     ($bin_name:expr, $region:expr) => {{
         let cit = $crate::CollectionIndexType::Default;
-        let region = as_geo!(String::from($region));
+        let region = $crate::Value::GeoJSON($crate::ToGeoJson::to_geojson(&$region));
         $crate::query::Filter::new(
             $bin_name,
             cit,
@@ -160,7 +192,7 @@ macro_rules! as_within_region {
         )
     }};
     ($bin_name:expr, $region:expr, $cit:expr) => {{
-        let region = as_geo!(String::from($region));
+        let region = $crate::Value::GeoJSON($crate::ToGeoJson::to_geojson(&$region));
         $crate::query::Filter::new(
             $bin_name,
             $cit,
@@ -181,7 +213,7 @@ macro_rules! as_within_radius {
         let lng = as_val!($lng as f64);
         let radius = as_val!($radius as f64);
         let geo_json = format!(
-            "{{ \"type\": \"Aeroircle\", \"coordinates\": [[{:.8}, {:.8}], {}] }}",
+            "{{ \"type\": \"AeroCircle\", \"coordinates\": [[{:.8}, {:.8}], {}] }}",
             lng, lat, radius
         );
         let geo_json = as_geo!(geo_json);
@@ -198,7 +230,7 @@ macro_rules! as_within_radius {
         let lng = as_val!($lng as f64);
         let radius = as_val!($radius as f64);
         let geo_json = format!(
-            "{{ \"type\": \"Aeroircle\", \"coordinates\": [[{:.8}, {:.8}], {}] }}",
+            "{{ \"type\": \"AeroCircle\", \"coordinates\": [[{:.8}, {:.8}], {}] }}",
             lng, lat, radius
         );
         let geo_json = as_geo!(geo_json);
@@ -212,13 +244,14 @@ macro_rules! as_within_radius {
     }};
 }
 
-/// Create geospatial "regions containing point" filter for queries. For queries on a collection
-/// index the collection index type must be specified.
+/// Create geospatial "regions containing point" filter for queries. The point can be given as a
+/// GeoJSON string or as a `GeoPoint`. For queries on a collection index the collection index type
+/// must be specified.
 #[macro_export]
 macro_rules! as_regions_containing_point {
     ($bin_name:expr, $point:expr) => {{
         let cit = $crate::CollectionIndexType::Default;
-        let point = as_geo!(String::from($point));
+        let point = $crate::Value::GeoJSON($crate::ToGeoJson::to_geojson(&$point));
         $crate::query::Filter::new(
             $bin_name,
             cit,
@@ -228,7 +261,7 @@ macro_rules! as_regions_containing_point {
         )
     }};
     ($bin_name:expr, $point:expr, $cit:expr) => {{
-        let point = as_geo!(String::from($point));
+        let point = $crate::Value::GeoJSON($crate::ToGeoJson::to_geojson(&$point));
         $crate::query::Filter::new(
             $bin_name,
             $cit,
@@ -242,6 +275,7 @@ macro_rules! as_regions_containing_point {
 #[cfg(test)]
 mod tests {
     use super::CollectionIndexType;
+    use crate::{GeoCircle, GeoPoint, Value};
 
     #[test]
     fn geo_filter_macros() {
@@ -262,5 +296,13 @@ mod tests {
 
         let geo_filter = as_within_radius!("bin1", 1, 3, 7, CollectionIndexType::List);
         assert_eq!(geo_filter.bin_name, "bin1");
+
+        let point = GeoPoint::new(-122.0, 37.5).unwrap();
+        let geo_filter = as_regions_containing_point!("bin1", point);
+        assert_eq!(geo_filter.begin, Value::from(point));
+
+        let circle = GeoCircle::new(point, 1000.0).unwrap();
+        let geo_filter = as_within_region!("bin1", circle, CollectionIndexType::List);
+        assert_eq!(geo_filter.begin, as_geo!(circle.to_string()));
     }
 }
@@ -0,0 +1,250 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use crate::errors::{ErrorKind, Result};
+use crate::operations::cdt_context::CdtContext;
+use crate::query::{CollectionIndexType, IndexType};
+
+/// Secondary index definition and statistics, as returned by `Client::list_indexes`.
+#[derive(Debug, Clone)]
+pub struct IndexInfo {
+    /// Namespace of the index.
+    pub namespace: String,
+
+    /// Name of the index.
+    pub name: String,
+
+    /// Set the index is restricted to, if any.
+    pub set_name: Option<String>,
+
+    /// Name of the indexed bin.
+    pub bin_name: String,
+
+    /// Data type of the indexed values.
+    pub index_type: IndexType,
+
+    /// Collection index type of the index.
+    pub collection_index_type: CollectionIndexType,
+
+    /// Context path of the indexed element, for indexes on nested list or map elements.
+    pub context: Vec<CdtContext>,
+
+    /// Index state as reported by the server, e.g. `RW` once the index is readable and writable.
+    pub state: String,
+
+    /// Load progress in percent. Across the cluster, this is the lowest progress of all nodes.
+    pub load_pct: u8,
+
+    /// Number of index entries. Across the cluster, this is the sum of the entries of all nodes.
+    pub entries: u64,
+}
+
+impl IndexInfo {
+    // Parses the response of the `sindex-list` info command. Each index is described by a list of
+    // colon separated `key=value` pairs; indexes are separated by semicolons.
+    pub(crate) fn parse_list(response: &str) -> Result<Vec<Self>> {
+        response
+            .split(';')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(Self::parse_entry)
+            .collect()
+    }
+
+    fn parse_entry(entry: &str) -> Result<Self> {
+        let fields: HashMap<&str, &str> = entry
+            .split(':')
+            .filter_map(|field| field.split_once('='))
+            .collect();
+
+        let field = |name: &str| {
+            fields.get(name).copied().ok_or_else(|| {
+                ErrorKind::BadResponse(format!("Missing `{}` in index info: {}", name, entry))
+            })
+        };
+
+        let set_name = fields
+            .get("set")
+            .filter(|set| !set.is_empty() && !set.eq_ignore_ascii_case("NULL"))
+            .map(|set| (*set).to_string());
+        // Older servers report the bin as `bins`.
+        let bin_name = field("bin").or_else(|_| field("bins"))?;
+        let context = match fields.get("context") {
+            Some(ctx) if !ctx.is_empty() && !ctx.eq_ignore_ascii_case("NULL") => {
+                CdtContext::from_base64(ctx)?
+            }
+            _ => vec![],
+        };
+
+        Ok(IndexInfo {
+            namespace: field("ns")?.to_string(),
+            name: field("indexname")?.to_string(),
+            set_name,
+            bin_name: bin_name.to_string(),
+            index_type: Self::parse_index_type(field("type")?)?,
+            collection_index_type: Self::parse_collection_index_type(
+                fields.get("indextype").copied().unwrap_or("default"),
+            )?,
+            context,
+            state: fields.get("state").copied().unwrap_or_default().to_string(),
+            load_pct: 0,
+            entries: 0,
+        })
+    }
+
+    fn parse_index_type(index_type: &str) -> Result<IndexType> {
+        match index_type.to_ascii_uppercase().as_str() {
+            "NUMERIC" | "INT SIGNED" => Ok(IndexType::Numeric),
+            "STRING" | "TEXT" => Ok(IndexType::String),
+            "GEO2DSPHERE" | "GEOJSON" => Ok(IndexType::Geo2DSphere),
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Unknown index type: {}",
+                index_type
+            ))),
+        }
+    }
+
+    fn parse_collection_index_type(collection_index_type: &str) -> Result<CollectionIndexType> {
+        match collection_index_type.to_ascii_uppercase().as_str() {
+            "DEFAULT" | "NONE" => Ok(CollectionIndexType::Default),
+            "LIST" => Ok(CollectionIndexType::List),
+            "MAPKEYS" => Ok(CollectionIndexType::MapKeys),
+            "MAPVALUES" => Ok(CollectionIndexType::MapValues),
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Unknown index collection type: {}",
+                collection_index_type
+            ))),
+        }
+    }
+
+    // Parses the `load_pct` of a `sindex/<ns>/<name>` response. Returns `None` if the index does
+    // not exist on the node.
+    pub(crate) fn parse_load_pct(response: &str) -> Result<Option<u8>> {
+        let stats = Self::parse_stats(response)?;
+        match stats.get("load_pct") {
+            None => Ok(None),
+            Some(pct) => match pct.parse::<u8>() {
+                Ok(pct) => Ok(Some(pct)),
+                Err(_) => bail!(ErrorKind::BadResponse(
+                    "Unexpected load_pct value from server".to_string()
+                )),
+            },
+        }
+    }
+
+    // Adds the statistics of one node, given as the response of the `sindex/<ns>/<name>` info
+    // command, to the cluster-wide statistics.
+    pub(crate) fn add_node_stats(&mut self, response: &str) -> Result<()> {
+        let stats = Self::parse_stats(response)?;
+        // A node without the index has not started loading it yet.
+        let load_pct = match stats.get("load_pct") {
+            Some(pct) => pct.parse()?,
+            None => 0,
+        };
+        self.load_pct = self.load_pct.min(load_pct);
+        if let Some(entries) = stats.get("entries") {
+            self.entries += entries.parse::<u64>()?;
+        }
+        Ok(())
+    }
+
+    fn parse_stats(response: &str) -> Result<HashMap<&str, &str>> {
+        if response.starts_with("FAIL:201") || response.starts_with("FAIL:203") {
+            return Ok(HashMap::new());
+        }
+
+        let stats: HashMap<&str, &str> = response
+            .split(';')
+            .filter_map(|stat| stat.split_once('='))
+            .collect();
+        if stats.is_empty() {
+            bail!(ErrorKind::BadResponse(format!(
+                "Unexpected index statistics. Response: {}",
+                response
+            )));
+        }
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexInfo;
+    use crate::operations::cdt_context::{ctx_map_key, CdtContext};
+    use crate::query::{CollectionIndexType, IndexType};
+    use crate::Value;
+
+    #[test]
+    fn parse_list() {
+        let ctx = CdtContext::to_base64(&[ctx_map_key(Value::from("address"))]);
+        let response = format!(
+            "ns=test:indexname=idx_a:set=demo:bin=a:type=numeric:indextype=default:context=NULL:state=RW;\
+             ns=test:indexname=idx_b:set=NULL:bin=b:type=string:indextype=mapvalues:context={}:state=WO;",
+            ctx
+        );
+        let indexes = IndexInfo::parse_list(&response).unwrap();
+        assert_eq!(indexes.len(), 2);
+
+        assert_eq!(indexes[0].name, "idx_a");
+        assert_eq!(indexes[0].set_name, Some("demo".to_string()));
+        assert_eq!(indexes[0].bin_name, "a");
+        assert_eq!(indexes[0].index_type, IndexType::Numeric);
+        assert_eq!(
+            indexes[0].collection_index_type,
+            CollectionIndexType::Default
+        );
+        assert!(indexes[0].context.is_empty());
+        assert_eq!(indexes[0].state, "RW");
+
+        assert_eq!(indexes[1].set_name, None);
+        assert_eq!(indexes[1].index_type, IndexType::String);
+        assert_eq!(
+            indexes[1].collection_index_type,
+            CollectionIndexType::MapValues
+        );
+        assert_eq!(indexes[1].context.len(), 1);
+
+        // older servers
+        let response = "ns=test:set=demo:indexname=idx_c:num_bins=1:bins=c:type=NUMERIC:sync_state=synced:state=RW";
+        let indexes = IndexInfo::parse_list(response).unwrap();
+        assert_eq!(indexes[0].bin_name, "c");
+
+        assert!(IndexInfo::parse_list("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_stats() {
+        assert_eq!(
+            IndexInfo::parse_load_pct("entries=10;load_pct=42").unwrap(),
+            Some(42)
+        );
+        assert_eq!(
+            IndexInfo::parse_load_pct("FAIL:201:no index").unwrap(),
+            None
+        );
+        assert!(IndexInfo::parse_load_pct("garbage").is_err());
+
+        let mut index =
+            IndexInfo::parse_list("ns=test:indexname=i:bin=a:type=numeric").unwrap()[0].clone();
+        index.load_pct = 100;
+        index.add_node_stats("entries=10;load_pct=100").unwrap();
+        index.add_node_stats("entries=5;load_pct=60").unwrap();
+        assert_eq!(index.entries, 15);
+        assert_eq!(index.load_pct, 60);
+        index.add_node_stats("FAIL:201:no index").unwrap();
+        assert_eq!(index.load_pct, 0);
+    }
+}
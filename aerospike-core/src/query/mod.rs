@@ -16,13 +16,19 @@
 //! Types and methods used for database queries and scans.
 #![allow(clippy::missing_errors_doc)]
 
+pub use self::aggregator::Aggregator;
 pub use self::filter::Filter;
+pub use self::index_info::IndexInfo;
 pub use self::index_types::{CollectionIndexType, IndexType};
 pub use self::recordset::Recordset;
 pub use self::statement::Statement;
-pub use self::udf::UDFLang;
+pub use self::udf::{UDFInfo, UDFLang};
 
+#[cfg(feature = "lua")]
+pub(crate) mod aggregate;
+mod aggregator;
 mod filter;
+mod index_info;
 mod index_types;
 mod recordset;
 mod statement;
@@ -21,7 +21,7 @@ use std::thread;
 use crossbeam_queue::SegQueue;
 use rand::Rng;
 
-use crate::errors::Result;
+use crate::errors::{Error, Result};
 use crate::Record;
 
 /// Virtual collection of records retrieved through queries and scans. During a query/scan,
@@ -75,6 +75,35 @@ impl Recordset {
         Some(record)
     }
 
+    // Pops the next record off the queue, yielding to the runtime while the queue is empty.
+    // Returns `None` once the scan/query is done and all records have been consumed.
+    pub(crate) async fn next_record(&self) -> Option<Result<Record>> {
+        loop {
+            let active = self.is_active();
+            if let Some(result) = self.record_queue.pop() {
+                self.record_queue_count.fetch_sub(1, Ordering::Relaxed);
+                return Some(result);
+            }
+            if !active {
+                return None;
+            }
+            aerospike_rt::task::yield_now().await;
+        }
+    }
+
+    // Pushes an error onto the queue, yielding to the runtime while the queue is full, so the
+    // error is not lost. Gives up once the consumer has closed the recordset.
+    pub(crate) async fn push_error(&self, err: Error) {
+        let mut result = Err(err);
+        while let Some(returned) = self.push(result) {
+            if !self.is_active() {
+                return;
+            }
+            result = returned;
+            aerospike_rt::task::yield_now().await;
+        }
+    }
+
     /// Returns the task ID for the scan/query.
     pub fn task_id(&self) -> u64 {
         self.task_id.load(Ordering::Relaxed) as u64
@@ -15,15 +15,17 @@
 
 use std::fmt;
 
+use crate::errors::{ErrorKind, Result};
+
 /// User-defined function (UDF) language
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UDFLang {
     /// Lua embedded programming language.
     Lua,
 }
 
 impl fmt::Display for UDFLang {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let s = match *self {
             UDFLang::Lua => "LUA",
         };
@@ -39,3 +41,82 @@ impl<'a> From<UDFLang> for &'a str {
         }
     }
 }
+
+/// User-defined function (UDF) package registered with the cluster, as returned by
+/// `Client::list_udfs`.
+#[derive(Debug, Clone)]
+pub struct UDFInfo {
+    /// File name of the UDF package, e.g. `example.lua`.
+    pub package_name: String,
+
+    /// SHA-1 hash of the package source, in lowercase hex.
+    pub hash: String,
+
+    /// Language of the package.
+    pub language: UDFLang,
+}
+
+impl UDFInfo {
+    // Parses the response of the `udf-list` info command. Each package is described by a list of
+    // comma separated `key=value` pairs; packages are separated by semicolons.
+    pub(crate) fn parse_list(response: &str) -> Result<Vec<Self>> {
+        let mut udfs = vec![];
+        for entry in response.split(';').filter(|entry| !entry.trim().is_empty()) {
+            let mut package_name = None;
+            let mut hash = None;
+            let mut language = None;
+            for (key, value) in entry.split(',').filter_map(|field| field.split_once('=')) {
+                match key {
+                    "filename" => package_name = Some(value.to_string()),
+                    "hash" => hash = Some(value.to_lowercase()),
+                    "type" => language = Some(UDFLang::parse(value)?),
+                    _ => {}
+                }
+            }
+
+            match (package_name, hash, language) {
+                (Some(package_name), Some(hash), Some(language)) => udfs.push(UDFInfo {
+                    package_name,
+                    hash,
+                    language,
+                }),
+                _ => bail!(ErrorKind::BadResponse(format!(
+                    "Invalid UDF info: {}",
+                    entry
+                ))),
+            }
+        }
+        Ok(udfs)
+    }
+}
+
+impl UDFLang {
+    fn parse(lang: &str) -> Result<Self> {
+        match lang.to_ascii_uppercase().as_str() {
+            "LUA" => Ok(UDFLang::Lua),
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Unknown UDF language: {}",
+                lang
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UDFInfo, UDFLang};
+
+    #[test]
+    fn parse_list() {
+        let response = "filename=a.lua,hash=1AD5F1C2,type=LUA;filename=b.lua,hash=ff00,type=LUA;";
+        let udfs = UDFInfo::parse_list(response).unwrap();
+        assert_eq!(udfs.len(), 2);
+        assert_eq!(udfs[0].package_name, "a.lua");
+        assert_eq!(udfs[0].hash, "1ad5f1c2");
+        assert_eq!(udfs[0].language, UDFLang::Lua);
+        assert_eq!(udfs[1].package_name, "b.lua");
+
+        assert!(UDFInfo::parse_list("").unwrap().is_empty());
+        assert!(UDFInfo::parse_list("filename=c.lua").is_err());
+    }
+}
@@ -16,10 +16,19 @@
 #[cfg(feature = "serialization")]
 use serde::Serialize;
 
+use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
+use std::str;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use byteorder::{ByteOrder, NetworkEndian};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
+use crate::errors::{Error, ErrorKind, Result};
+use crate::value::ValueRef;
 use crate::Key;
 use crate::Value;
 
@@ -66,23 +75,200 @@ impl Record {
     /// Returns the remaining time-to-live (TTL, a.k.a. expiration time) for the record or `None`
     /// if the record never expires.
     pub fn time_to_live(&self) -> Option<Duration> {
-        match self.expiration {
-            0 => None,
-            secs_since_epoch => {
-                let expiration = *CITRUSLEAF_EPOCH + Duration::new(u64::from(secs_since_epoch), 0);
-                match expiration.duration_since(SystemTime::now()) {
-                    Ok(d) => Some(d),
-                    // Record was not expired at server but it looks expired at client
-                    // because of delay or clock difference, present it as not-expired.
-                    Err(_) => Some(Duration::new(1u64, 0)),
+        time_to_live(self.expiration)
+    }
+
+    /// Returns the time at which the record expires or `None` if the record never expires.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        expires_at(self.expiration)
+    }
+
+    /// Returns the time at which the record expires as a UTC date and time, or `None` if the
+    /// record never expires.
+    #[cfg(feature = "chrono")]
+    pub fn expires_at_utc(&self) -> Option<DateTime<Utc>> {
+        self.expires_at().map(DateTime::from)
+    }
+
+    /// Returns the value of the named bin, converted into `T`. A missing bin is treated as
+    /// `Value::Nil`, i.e. it converts to `None` if `T` is an `Option`.
+    ///
+    /// Returns an `ErrorKind::BinConversion` error naming the bin and the actual value type if
+    /// the value cannot be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// # use std::collections::HashMap;
+    ///
+    /// let mut bins = HashMap::new();
+    /// bins.insert("name".to_string(), Value::from("Alice"));
+    /// bins.insert("age".to_string(), Value::from(32));
+    /// let record = Record::new(None, bins, 1, 0);
+    ///
+    /// let name: String = record.get("name").unwrap();
+    /// let age: u8 = record.get("age").unwrap();
+    /// let email: Option<String> = record.get("email").unwrap();
+    /// assert_eq!((name.as_str(), age, email), ("Alice", 32, None));
+    /// assert!(record.get::<i64>("name").is_err());
+    /// ```
+    pub fn get<'a, T>(&'a self, bin: &str) -> Result<T>
+    where
+        T: TryFrom<&'a Value, Error = Error>,
+    {
+        static NIL: Value = Value::Nil;
+        let value = self.bins.get(bin).unwrap_or(&NIL);
+        T::try_from(value).map_err(|err| match err.0 {
+            ErrorKind::ValueConversion(expected, actual) => {
+                ErrorKind::BinConversion(bin.to_string(), expected, actual).into()
+            }
+            kind => kind.into(),
+        })
+    }
+}
+
+fn expires_at(expiration: u32) -> Option<SystemTime> {
+    match expiration {
+        0 => None,
+        secs_since_epoch => Some(*CITRUSLEAF_EPOCH + Duration::new(u64::from(secs_since_epoch), 0)),
+    }
+}
+
+fn time_to_live(expiration: u32) -> Option<Duration> {
+    expires_at(expiration).map(|expiration| {
+        match expiration.duration_since(SystemTime::now()) {
+            Ok(d) => d,
+            // Record was not expired at server but it looks expired at client
+            // because of delay or clock difference, present it as not-expired.
+            Err(_) => Duration::new(1u64, 0),
+        }
+    })
+}
+
+/// Database record borrowed from the receive buffer of a connection, as passed to the closure of
+/// `Client::get_with`.
+///
+/// Bins are decoded lazily, only when accessed; string and blob values reference the received
+/// bytes instead of being copied. Use `RecordRef::to_record` to obtain an owned `Record`.
+#[derive(Debug, Clone, Copy)]
+pub struct RecordRef<'a> {
+    ops: &'a [u8],
+    op_count: usize,
+
+    /// Record modification count.
+    pub generation: u32,
+
+    expiration: u32,
+}
+
+impl<'a> RecordRef<'a> {
+    // Wraps the fields and operations of a response message. The fields are skipped.
+    pub(crate) fn new(
+        data: &'a [u8],
+        field_count: usize,
+        op_count: usize,
+        generation: u32,
+        expiration: u32,
+    ) -> Result<Self> {
+        let mut ops = data;
+        for _ in 0..field_count {
+            let field_size = read_size(ops)?;
+            ops = &ops[4 + field_size..];
+        }
+        Ok(RecordRef {
+            ops,
+            op_count,
+            generation,
+            expiration,
+        })
+    }
+
+    /// Returns the remaining time-to-live (TTL, a.k.a. expiration time) for the record or `None`
+    /// if the record never expires.
+    pub fn time_to_live(&self) -> Option<Duration> {
+        time_to_live(self.expiration)
+    }
+
+    /// Returns the time at which the record expires or `None` if the record never expires.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        expires_at(self.expiration)
+    }
+
+    /// Returns the value of the named bin, or `None` if the record does not contain the bin. Only
+    /// this bin's value is decoded.
+    pub fn bin(&self, name: &str) -> Result<Option<ValueRef<'a>>> {
+        for bin in self.bins() {
+            let (bin_name, value) = bin?;
+            if bin_name == name && !value.is_nil() {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns an iterator over the names and values of all bins returned by the server.
+    pub fn bins(&self) -> impl Iterator<Item = Result<(&'a str, ValueRef<'a>)>> + 'a {
+        let mut ops = self.ops;
+        (0..self.op_count).map(move |_| {
+            let op_size = read_size(ops)?;
+            let op = &ops[4..4 + op_size];
+            ops = &ops[4 + op_size..];
+
+            if op.len() < 4 || op.len() < 4 + op[3] as usize {
+                bail!(ErrorKind::BadResponse(
+                    "Truncated bin in response".to_string()
+                ));
+            }
+            let particle_type = op[1];
+            let name_size = op[3] as usize;
+            let name = str::from_utf8(&op[4..4 + name_size])?;
+            let value = ValueRef::from_particle(particle_type, &op[4 + name_size..])?;
+            Ok((name, value))
+        })
+    }
+
+    /// Decodes all bins into an owned `Record`.
+    pub fn to_record(&self) -> Result<Record> {
+        let mut bins: HashMap<String, Value> = HashMap::with_capacity(self.op_count);
+        for bin in self.bins() {
+            let (name, value) = bin?;
+            if value.is_nil() {
+                continue;
+            }
+
+            let value = value.to_value()?;
+            // list/map operations may return multiple values for the same bin.
+            match bins.entry(name.to_string()) {
+                Vacant(entry) => {
+                    entry.insert(value);
                 }
+                Occupied(entry) => match *entry.into_mut() {
+                    Value::List(ref mut list) => list.push(value),
+                    ref mut prev => {
+                        *prev = as_list!(prev.clone(), value);
+                    }
+                },
             }
         }
+        Ok(Record::new(None, bins, self.generation, self.expiration))
     }
 }
 
+// Reads the size prefix of a field or operation and checks that the data is complete.
+fn read_size(data: &[u8]) -> Result<usize> {
+    if data.len() < 4 {
+        bail!(ErrorKind::BadResponse("Truncated response".to_string()));
+    }
+    let size = NetworkEndian::read_u32(data) as usize;
+    if data.len() < 4 + size {
+        bail!(ErrorKind::BadResponse("Truncated response".to_string()));
+    }
+    Ok(size)
+}
+
 impl fmt::Display for Record {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "key: {:?}", self.key)?;
         write!(f, ", bins: {{")?;
         for (i, (k, v)) in self.bins.iter().enumerate() {
@@ -102,10 +288,80 @@ impl fmt::Display for Record {
 
 #[cfg(test)]
 mod tests {
-    use super::{Record, CITRUSLEAF_EPOCH};
+    use super::{Record, RecordRef, CITRUSLEAF_EPOCH};
+    use crate::value::ValueRef;
+    use crate::Value;
     use std::collections::HashMap;
     use std::time::{Duration, SystemTime};
 
+    // Serializes a bin as returned by the server.
+    fn op(name: &str, particle_type: u8, particle: &[u8]) -> Vec<u8> {
+        let size = 4 + name.len() + particle.len();
+        let mut op = (size as u32).to_be_bytes().to_vec();
+        op.extend_from_slice(&[1, particle_type, 0, name.len() as u8]);
+        op.extend_from_slice(name.as_bytes());
+        op.extend_from_slice(particle);
+        op
+    }
+
+    #[test]
+    fn get() {
+        let mut bins = HashMap::new();
+        bins.insert("int".to_string(), Value::from(42));
+        bins.insert("str".to_string(), Value::from("abc"));
+        let record = Record::new(None, bins, 0, 0);
+
+        assert_eq!(record.get::<i64>("int").unwrap(), 42);
+        assert_eq!(record.get::<String>("str").unwrap(), "abc");
+        assert_eq!(record.get::<Option<i64>>("missing").unwrap(), None);
+
+        let err = record.get::<i64>("str").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot convert string value of bin `str` to i64"
+        );
+        let err = record.get::<i64>("missing").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot convert nil value of bin `missing` to i64"
+        );
+    }
+
+    #[test]
+    fn record_ref() {
+        let mut data = vec![0, 0, 0, 2, 0, 0]; // field
+        data.extend(op("int", 1, &42i64.to_be_bytes()));
+        data.extend(op("str", 3, b"hello"));
+        data.extend(op("blob", 4, &[1, 2, 3]));
+        data.extend(op("list", 20, &[0x92, 0x01, 0x02]));
+        data.extend(op("nil", 0, &[]));
+
+        let record = RecordRef::new(&data, 1, 5, 3, 0).unwrap();
+        assert_eq!(record.generation, 3);
+        assert_eq!(record.time_to_live(), None);
+        assert_eq!(record.bin("int").unwrap(), Some(ValueRef::Int(42)));
+        assert_eq!(record.bin("str").unwrap(), Some(ValueRef::String("hello")));
+        assert_eq!(
+            record.bin("blob").unwrap(),
+            Some(ValueRef::Blob(&[1, 2, 3]))
+        );
+        assert_eq!(record.bin("nil").unwrap(), None);
+        assert_eq!(record.bin("missing").unwrap(), None);
+        assert_eq!(
+            record.bin("list").unwrap().unwrap().to_value().unwrap(),
+            as_list!(1, 2)
+        );
+
+        let record = record.to_record().unwrap();
+        assert_eq!(record.bins.len(), 4);
+        assert_eq!(record.bins["str"], Value::from("hello"));
+        assert_eq!(record.bins["blob"], Value::from(vec![1u8, 2, 3]));
+
+        // truncated responses are rejected
+        let record = RecordRef::new(&data[..data.len() - 10], 1, 5, 3, 0).unwrap();
+        assert!(record.to_record().is_err());
+    }
+
     #[test]
     fn ttl_expiration_future() {
         let expiration = SystemTime::now() + Duration::new(1000, 0);
@@ -129,5 +385,22 @@ mod tests {
     fn ttl_never_expires() {
         let record = Record::new(None, HashMap::new(), 0, 0);
         assert_eq!(record.time_to_live(), None);
+        assert_eq!(record.expires_at(), None);
+    }
+
+    #[test]
+    fn expires_at() {
+        // 2020-01-01 00:00:00 UTC
+        let record = Record::new(None, HashMap::new(), 0, 315_532_800);
+        assert_eq!(
+            record.expires_at(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_577_836_800))
+        );
+
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            record.expires_at_utc().unwrap().to_rfc3339(),
+            "2020-01-01T00:00:00+00:00"
+        );
     }
 }
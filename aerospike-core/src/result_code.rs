@@ -155,9 +155,36 @@ pub enum ResultCode {
     /// A user defined function returned an error code.
     UdfBadResponse,
 
-    /// The requested item in a large collection was not found.
+    /// The requested item in a large collection was not found. Only returned by legacy servers;
+    /// servers with transaction support use the same code for `MrtAborted`.
     LargeItemNotFound,
 
+    /// Transaction record blocked by a different transaction.
+    MrtBlocked,
+
+    /// Transaction read version mismatch identified during commit. Some other command changed
+    /// the record outside the scope of the transaction.
+    MrtVersionMismatch,
+
+    /// Transaction deadline reached without a successful commit or abort.
+    MrtExpired,
+
+    /// Transaction write command limit (4096) exceeded.
+    MrtTooManyWrites,
+
+    /// Transaction was already committed.
+    MrtCommitted,
+
+    /// Transaction was already aborted.
+    MrtAborted,
+
+    /// This record has been locked by a previous update in this transaction.
+    MrtAlreadyLocked,
+
+    /// This transaction has already started. Writing to the same transaction with independent
+    /// transaction monitor records is not allowed.
+    MrtMonitorExists,
+
     /// Batch functionality has been disabled.
     BatchDisabled,
 
@@ -260,7 +287,14 @@ impl ResultCode {
             80 => ResultCode::NotAuthenticated,
             81 => ResultCode::RoleViolation,
             100 => ResultCode::UdfBadResponse,
-            125 => ResultCode::LargeItemNotFound,
+            120 => ResultCode::MrtBlocked,
+            121 => ResultCode::MrtVersionMismatch,
+            122 => ResultCode::MrtExpired,
+            123 => ResultCode::MrtTooManyWrites,
+            124 => ResultCode::MrtCommitted,
+            125 => ResultCode::MrtAborted,
+            126 => ResultCode::MrtAlreadyLocked,
+            127 => ResultCode::MrtMonitorExists,
             150 => ResultCode::BatchDisabled,
             151 => ResultCode::BatchMaxRequestsExceeded,
             152 => ResultCode::BatchQueuesFull,
@@ -334,6 +368,18 @@ impl ResultCode {
             ResultCode::RoleViolation => String::from("Role violation"),
             ResultCode::UdfBadResponse => String::from("Udf returned error"),
             ResultCode::LargeItemNotFound => String::from("Large collection item not found"),
+            ResultCode::MrtBlocked => {
+                String::from("Transaction record blocked by a different transaction")
+            }
+            ResultCode::MrtVersionMismatch => String::from("Transaction version mismatch"),
+            ResultCode::MrtExpired => String::from("Transaction expired"),
+            ResultCode::MrtTooManyWrites => String::from("Transaction write command limit exceeded"),
+            ResultCode::MrtCommitted => String::from("Transaction already committed"),
+            ResultCode::MrtAborted => String::from("Transaction already aborted"),
+            ResultCode::MrtAlreadyLocked => {
+                String::from("Record locked by a previous update in this transaction")
+            }
+            ResultCode::MrtMonitorExists => String::from("Transaction monitor already exists"),
             ResultCode::BatchDisabled => String::from("Batch functionality has been disabled"),
             ResultCode::BatchMaxRequestsExceeded => {
                 String::from("Batch max requests have been exceeded")
@@ -384,6 +430,12 @@ mod tests {
         assert_eq!(ResultCode::KeyNotFoundError, ResultCode::from(2u8));
     }
 
+    #[test]
+    fn from_mrt_result_code() {
+        assert_eq!(ResultCode::MrtVersionMismatch, ResultCode::from(121u8));
+        assert_eq!(ResultCode::MrtAborted, ResultCode::from(125u8));
+    }
+
     #[test]
     fn from_unknown_result_code() {
         assert_eq!(ResultCode::Unknown(234), ResultCode::from(234u8));
@@ -15,6 +15,7 @@
 
 use crate::cluster::Cluster;
 use crate::errors::{ErrorKind, Result};
+use crate::query::IndexInfo;
 use crate::task::{Status, Task};
 use std::sync::Arc;
 
@@ -26,11 +27,6 @@ pub struct IndexTask {
     index_name: String,
 }
 
-static SUCCESS_PATTERN: &str = "load_pct=";
-static FAIL_PATTERN_201: &str = "FAIL:201";
-static FAIL_PATTERN_203: &str = "FAIL:203";
-static DELMITER: &str = ";";
-
 impl IndexTask {
     /// Initializes `IndexTask` from client, creation should only be expose to Client
     pub fn new(cluster: Arc<Cluster>, namespace: String, index_name: String) -> Self {
@@ -45,38 +41,30 @@ impl IndexTask {
         return format!("sindex/{}/{}", namespace, index_name);
     }
 
-    fn parse_response(response: &str) -> Result<Status> {
-        match response.find(SUCCESS_PATTERN) {
-            None => {
-                if response.contains(FAIL_PATTERN_201) || response.contains(FAIL_PATTERN_203) {
-                    Ok(Status::NotFound)
-                } else {
-                    bail!(ErrorKind::BadResponse(format!(
-                        "Code 201 and 203 missing. Response: {}",
-                        response
-                    )));
-                }
-            }
-            Some(pattern_index) => {
-                let percent_begin = pattern_index + SUCCESS_PATTERN.len();
+    /// Returns the load progress of the index in percent. As every node builds its part of the
+    /// index independently, this is the lowest progress of all nodes. Nodes that have not created
+    /// the index yet report no progress.
+    pub async fn load_progress(&self) -> Result<u8> {
+        let nodes = self.cluster.nodes();
 
-                let percent_end = match response[percent_begin..].find(DELMITER) {
-                    None => bail!(ErrorKind::BadResponse(format!(
-                        "delimiter missing in response. Response: {}",
-                        response
-                    ))),
-                    Some(percent_end) => percent_end,
-                };
-                let percent_str = &response[percent_begin..percent_begin + percent_end];
-                match percent_str.parse::<isize>() {
-                    Ok(100) => Ok(Status::Complete),
-                    Ok(_) => Ok(Status::InProgress),
-                    Err(_) => bail!(ErrorKind::BadResponse(
-                        "Unexpected load_pct value from server".to_string()
-                    )),
-                }
-            }
+        if nodes.is_empty() {
+            bail!(ErrorKind::Connection("No connected node".to_string()))
+        }
+
+        let command = &IndexTask::build_command(self.namespace.clone(), self.index_name.clone());
+        let commands = [&command[..]];
+        let responses =
+            futures::future::join_all(nodes.iter().map(|node| node.info(&commands))).await;
+        let mut progress = 100;
+        for response in responses {
+            let response = response?;
+            let load_pct = match response.get(command) {
+                Some(response) => IndexInfo::parse_load_pct(response)?.unwrap_or_default(),
+                None => 0,
+            };
+            progress = progress.min(load_pct);
         }
+        Ok(progress)
     }
 }
 
@@ -99,9 +87,10 @@ impl Task for IndexTask {
                 return Ok(Status::NotFound);
             }
 
-            match IndexTask::parse_response(&response[command]) {
-                Ok(Status::Complete) => {}
-                in_progress_or_error => return in_progress_or_error,
+            match IndexInfo::parse_load_pct(&response[command])? {
+                None => return Ok(Status::NotFound),
+                Some(100) => {}
+                Some(_) => return Ok(Status::InProgress),
             }
         }
         Ok(Status::Complete)
@@ -0,0 +1,398 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::errors::{ErrorKind, Result};
+use crate::{Key, Value};
+
+// Set name of the monitor records that track the keys written by a transaction.
+const MONITOR_SET_NAME: &str = "<ERO~MRT";
+
+/// State of a multi-record transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxnState {
+    /// The transaction accepts commands.
+    Open,
+
+    /// The read versions were verified during commit, but the commit has not completed yet.
+    Verified,
+
+    /// The transaction was committed.
+    Committed,
+
+    /// The transaction was aborted.
+    Aborted,
+}
+
+/// Outcome of a successful `Client::commit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStatus {
+    /// The transaction was committed.
+    Ok,
+
+    /// The transaction had already been committed.
+    AlreadyCommitted,
+
+    /// The transaction was committed, but rolling forward some of the written records failed.
+    /// The server will roll them forward once the transaction deadline has passed.
+    RollForwardAbandoned,
+
+    /// The transaction was committed, but the monitor record could not be removed. The server
+    /// will remove it once the transaction deadline has passed.
+    CloseAbandoned,
+}
+
+/// Outcome of a successful `Client::abort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbortStatus {
+    /// The transaction was aborted.
+    Ok,
+
+    /// The transaction had already been aborted.
+    AlreadyAborted,
+
+    /// The transaction was aborted, but rolling back some of the written records failed. The
+    /// server will roll them back once the transaction deadline has passed.
+    RollBackAbandoned,
+
+    /// The transaction was aborted, but the monitor record could not be removed. The server will
+    /// remove it once the transaction deadline has passed.
+    CloseAbandoned,
+}
+
+/// Reason why a transaction could not be committed. Returned as part of `ErrorKind::TxnCommit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitError {
+    /// A record read in the transaction was modified by another command. The transaction was
+    /// aborted.
+    VerifyFail,
+
+    /// Verification failed and the transaction was aborted, but the monitor record could not be
+    /// removed.
+    VerifyFailCloseAbandoned,
+
+    /// Verification failed, but rolling back the written records failed. The server will roll
+    /// them back once the transaction deadline has passed.
+    VerifyFailAbortAbandoned,
+
+    /// The monitor record could not be marked for roll-forward.
+    MarkRollForwardAbandoned,
+}
+
+impl fmt::Display for CommitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let desc = match self {
+            CommitError::VerifyFail => "transaction verify failed, transaction aborted",
+            CommitError::VerifyFailCloseAbandoned => {
+                "transaction verify failed, transaction aborted, monitor close abandoned"
+            }
+            CommitError::VerifyFailAbortAbandoned => {
+                "transaction verify failed, transaction abort abandoned"
+            }
+            CommitError::MarkRollForwardAbandoned => "transaction mark roll forward abandoned",
+        };
+        f.write_str(desc)
+    }
+}
+
+/// Handle of a multi-record transaction, as returned by `Client::begin_transaction`.
+///
+/// A command takes part in the transaction when the transaction is set as the `txn` of its
+/// policy. Clones of the handle refer to the same transaction. All records of a transaction must
+/// belong to the same namespace. The transaction ends with `Client::commit` or `Client::abort`.
+#[derive(Debug, Clone)]
+pub struct Txn {
+    inner: Arc<TxnInner>,
+}
+
+#[derive(Debug)]
+struct TxnInner {
+    id: i64,
+    data: Mutex<TxnData>,
+}
+
+#[derive(Debug)]
+struct TxnData {
+    namespace: Option<String>,
+    // Versions of the records read in the transaction, by digest.
+    reads: HashMap<[u8; 20], (Key, u64)>,
+    // Records written in the transaction, by digest.
+    writes: HashMap<[u8; 20], Key>,
+    timeout: u32,
+    deadline: u32,
+    state: TxnState,
+    monitor_in_doubt: bool,
+    // Set if the outcome of a write is unknown. The monitor record is then left for the server
+    // to remove, as the write may still be applied after the transaction was rolled.
+    write_in_doubt: bool,
+    in_doubt: bool,
+}
+
+// Transaction fields sent along with a single record command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxnFields {
+    pub id: i64,
+    pub version: Option<u64>,
+    pub deadline: Option<u32>,
+}
+
+impl Txn {
+    /// Creates a new transaction with a random id.
+    pub fn new() -> Self {
+        let mut id = 0;
+        while id == 0 {
+            id = rand::random::<i64>();
+        }
+        Txn {
+            inner: Arc::new(TxnInner {
+                id,
+                data: Mutex::new(TxnData {
+                    namespace: None,
+                    reads: HashMap::new(),
+                    writes: HashMap::new(),
+                    timeout: 0,
+                    deadline: 0,
+                    state: TxnState::Open,
+                    monitor_in_doubt: false,
+                    write_in_doubt: false,
+                    in_doubt: false,
+                }),
+            }),
+        }
+    }
+
+    /// Transaction id.
+    pub fn id(&self) -> i64 {
+        self.inner.id
+    }
+
+    /// Namespace of the transaction, once the first command has been run in it.
+    pub fn namespace(&self) -> Option<String> {
+        self.data().namespace.clone()
+    }
+
+    /// Sets the time after which the server aborts the transaction if it has not been committed.
+    /// The timer starts with the first write. A timeout of zero, the default, uses the server's
+    /// `mrt-duration` setting. Must be set before the first write.
+    pub fn set_timeout(&self, timeout: Duration) {
+        self.data().timeout = timeout.as_secs() as u32;
+    }
+
+    /// Transaction timeout, in seconds.
+    pub fn timeout(&self) -> u32 {
+        self.data().timeout
+    }
+
+    /// Current state of the transaction.
+    pub fn state(&self) -> TxnState {
+        self.data().state
+    }
+
+    /// Whether the outcome of a previous commit attempt is unknown. If set, the commit can be
+    /// retried.
+    pub fn in_doubt(&self) -> bool {
+        self.data().in_doubt
+    }
+
+    fn data(&self) -> MutexGuard<'_, TxnData> {
+        self.inner.data.lock().unwrap()
+    }
+
+    // Checks that the command on `key` can run in the transaction. The first command sets the
+    // transaction's namespace.
+    pub(crate) fn verify_command(&self, key: &Key) -> Result<()> {
+        let mut data = self.data();
+        if data.state != TxnState::Open {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Command not allowed in current transaction state: {:?}",
+                data.state
+            )));
+        }
+        match data.namespace {
+            Some(ref ns) if *ns != key.namespace => {
+                bail!(ErrorKind::InvalidArgument(format!(
+                    "Namespace must be the same for all commands in the transaction: {} != {}",
+                    ns, key.namespace
+                )));
+            }
+            Some(_) => (),
+            None => data.namespace = Some(key.namespace.clone()),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn fields(&self, key: &Key, has_write: bool) -> TxnFields {
+        let data = self.data();
+        TxnFields {
+            id: self.inner.id,
+            version: data.reads.get(&key.digest).map(|&(_, version)| version),
+            deadline: if has_write && data.deadline != 0 {
+                Some(data.deadline)
+            } else {
+                None
+            },
+        }
+    }
+
+    pub(crate) fn on_read(&self, key: &Key, version: Option<u64>) {
+        if let Some(version) = version {
+            self.data().reads.insert(key.digest, (key.clone(), version));
+        }
+    }
+
+    pub(crate) fn on_write(&self, key: &Key, version: Option<u64>, succeeded: bool) {
+        let mut data = self.data();
+        if let Some(version) = version {
+            data.reads.insert(key.digest, (key.clone(), version));
+        } else if succeeded {
+            data.reads.remove(&key.digest);
+            data.writes.insert(key.digest, key.clone());
+        }
+    }
+
+    // The write may or may not have been applied, so the record has to be rolled either way.
+    pub(crate) fn on_write_in_doubt(&self, key: &Key) {
+        let mut data = self.data();
+        data.reads.remove(&key.digest);
+        data.writes.insert(key.digest, key.clone());
+        data.write_in_doubt = true;
+    }
+
+    pub(crate) fn is_written(&self, key: &Key) -> bool {
+        self.data().writes.contains_key(&key.digest)
+    }
+
+    pub(crate) fn reads(&self) -> Vec<(Key, u64)> {
+        self.data().reads.values().cloned().collect()
+    }
+
+    pub(crate) fn writes(&self) -> Vec<Key> {
+        self.data().writes.values().cloned().collect()
+    }
+
+    pub(crate) fn set_deadline(&self, deadline: u32) {
+        self.data().deadline = deadline;
+    }
+
+    pub(crate) fn monitor_exists(&self) -> bool {
+        self.data().deadline != 0
+    }
+
+    pub(crate) fn monitor_might_exist(&self) -> bool {
+        let data = self.data();
+        data.deadline != 0 || data.monitor_in_doubt
+    }
+
+    // The monitor record is only closed by the client if no write is in doubt.
+    pub(crate) fn close_monitor(&self) -> bool {
+        self.monitor_might_exist() && !self.data().write_in_doubt
+    }
+
+    pub(crate) fn set_monitor_in_doubt(&self) {
+        self.data().monitor_in_doubt = true;
+    }
+
+    pub(crate) fn set_state(&self, state: TxnState) {
+        self.data().state = state;
+    }
+
+    pub(crate) fn set_in_doubt(&self, in_doubt: bool) {
+        self.data().in_doubt = in_doubt;
+    }
+
+    // Key of the monitor record, which lists the keys written by the transaction so that the
+    // server can roll them forward or back if the client does not complete the transaction.
+    pub(crate) fn monitor_key(&self) -> Result<Key> {
+        let namespace = self.data().namespace.clone().unwrap_or_default();
+        Key::new(
+            namespace,
+            MONITOR_SET_NAME.to_string(),
+            Value::Int(self.inner.id),
+        )
+    }
+}
+
+impl Default for Txn {
+    fn default() -> Self {
+        Txn::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Txn, TxnFields, TxnState};
+    use crate::Key;
+
+    #[test]
+    fn namespace() {
+        let txn = Txn::new();
+        assert_ne!(txn.id(), 0);
+        assert_eq!(txn.namespace(), None);
+
+        txn.verify_command(&as_key!("test", "set", 1)).unwrap();
+        txn.verify_command(&as_key!("test", "other", 2)).unwrap();
+        assert_eq!(txn.namespace(), Some(String::from("test")));
+        assert!(txn.verify_command(&as_key!("bar", "set", 1)).is_err());
+
+        txn.set_state(TxnState::Committed);
+        assert!(txn.verify_command(&as_key!("test", "set", 1)).is_err());
+    }
+
+    #[test]
+    fn reads_and_writes() {
+        let txn = Txn::new();
+        let key1: Key = as_key!("test", "set", 1);
+        let key2: Key = as_key!("test", "set", 2);
+
+        txn.on_read(&key1, Some(7));
+        txn.on_read(&key2, None);
+        assert_eq!(txn.reads().len(), 1);
+        assert_eq!(
+            txn.fields(&key1, true),
+            TxnFields {
+                id: txn.id(),
+                version: Some(7),
+                deadline: None,
+            }
+        );
+
+        txn.set_deadline(100);
+        assert!(txn.monitor_exists());
+        assert_eq!(txn.fields(&key2, true).deadline, Some(100));
+        assert_eq!(txn.fields(&key2, false).deadline, None);
+
+        // a failed write keeps the read version
+        txn.on_write(&key1, None, false);
+        assert!(!txn.is_written(&key1));
+        txn.on_write(&key1, None, true);
+        assert!(txn.is_written(&key1));
+        assert!(txn.reads().is_empty());
+
+        assert!(txn.close_monitor());
+        txn.on_write_in_doubt(&key2);
+        assert_eq!(txn.writes().len(), 2);
+        assert!(txn.monitor_might_exist());
+        assert!(!txn.close_monitor());
+
+        // clones share the transaction
+        let clone = txn.clone();
+        clone.set_state(TxnState::Aborted);
+        assert_eq!(txn.state(), TxnState::Aborted);
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use crate::Value;
+
+/// Modification of a record, as returned by the update function of `Client::update_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Update {
+    /// Write the given bins. Bins set to `Value::Nil` are removed. Whether other existing bins are
+    /// kept depends on the `record_exists_action` of the write policy.
+    Put(HashMap<String, Value>),
+
+    /// Delete the record.
+    Delete,
+
+    /// Leave the record unchanged.
+    Abort,
+}
@@ -14,11 +14,14 @@
 // the License.
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::result::Result as StdResult;
 
 use byteorder::{ByteOrder, NetworkEndian};
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
 
 use ripemd::digest::Digest;
 use ripemd::Ripemd160;
@@ -27,7 +30,7 @@ use std::vec::Vec;
 
 use crate::commands::buffer::Buffer;
 use crate::commands::ParticleType;
-use crate::errors::Result;
+use crate::errors::{Error, ErrorKind, Result};
 use crate::msgpack::{decoder, encoder};
 
 #[cfg(feature = "serialization")]
@@ -182,9 +185,11 @@ pub enum Value {
     /// type.
     HashMap(HashMap<Value, Value>),
 
-    /// Map data type where the map entries are sorted based key ordering (K-ordered maps) and may
-    /// have an additional value-order index depending the namespace configuration (KV-ordered
-    /// maps).
+    /// Map data type where the order of the map entries is preserved. Returned for K-ordered and
+    /// KV-ordered maps, whose entries are sorted by key, and for map operation results where the
+    /// range order needs to be preserved, e.g. key/value results of rank range getters. When
+    /// written, the entries are sent in order; the order of the stored map is determined by the
+    /// map policy.
     OrderedMap(Vec<(Value, Value)>),
 
     /// GeoJSON data type are JSON formatted strings to encode geospatial information.
@@ -221,6 +226,78 @@ impl Value {
         matches!(*self, Value::Nil)
     }
 
+    /// Returns the name of the value's type, e.g. `int` or `string`, as used in error messages.
+    pub const fn type_name(&self) -> &'static str {
+        match *self {
+            Value::Nil => "nil",
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "int",
+            Value::UInt(_) => "uint",
+            Value::Float(_) => "float",
+            Value::String(_) => "string",
+            Value::Blob(_) => "blob",
+            Value::List(_) => "list",
+            Value::HashMap(_) | Value::OrderedMap(_) => "map",
+            Value::GeoJSON(_) => "geojson",
+            Value::HLL(_) => "hll",
+        }
+    }
+
+    /// Returns the integer value, or `None` if the value is not an integer.
+    pub const fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Int(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the floating point value, or `None` if the value is not a float.
+    pub fn as_f64(&self) -> Option<f64> {
+        f64::try_from(self).ok()
+    }
+
+    /// Returns the boolean value, or `None` if the value is not a boolean.
+    pub const fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value, or `None` if the value is not a string or GeoJSON value.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Value::String(ref val) | Value::GeoJSON(ref val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes of a blob or HLL value, or `None` for other values.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match *self {
+            Value::Blob(ref val) | Value::HLL(ref val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of a list value, or `None` if the value is not a list.
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match *self {
+            Value::List(ref val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the given map key, or `None` if the value is not a map or does not
+    /// contain the key.
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        match *self {
+            Value::HashMap(ref val) => val.get(key),
+            Value::OrderedMap(ref val) => val.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
     /// Return the particle type for the value used in the wire protocol.
     /// For internal use only.
     #[doc(hidden)]
@@ -236,8 +313,7 @@ impl Value {
             Value::String(_) => ParticleType::STRING,
             Value::Blob(_) => ParticleType::BLOB,
             Value::List(_) => ParticleType::LIST,
-            Value::HashMap(_) => ParticleType::MAP,
-            Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
+            Value::HashMap(_) | Value::OrderedMap(_) => ParticleType::MAP,
             Value::GeoJSON(_) => ParticleType::GEOJSON,
             Value::HLL(_) => ParticleType::HLL,
         }
@@ -272,8 +348,9 @@ impl Value {
             ),
             Value::String(ref s) => s.len(),
             Value::Blob(ref b) => b.len(),
-            Value::List(_) | Value::HashMap(_) => encoder::pack_value(&mut None, self),
-            Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
+            Value::List(_) | Value::HashMap(_) | Value::OrderedMap(_) => {
+                encoder::pack_value(&mut None, self)
+            }
             Value::GeoJSON(ref s) => 1 + 2 + s.len(), // flags + ncells + jsonstr
             Value::HLL(ref h) => h.len(),
         }
@@ -294,8 +371,9 @@ impl Value {
             Value::Float(ref val) => buf.write_f64(f64::from(val)),
             Value::String(ref val) => buf.write_str(val),
             Value::Blob(ref val) | Value::HLL(ref val) => buf.write_bytes(val),
-            Value::List(_) | Value::HashMap(_) => encoder::pack_value(&mut Some(buf), self),
-            Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
+            Value::List(_) | Value::HashMap(_) | Value::OrderedMap(_) => {
+                encoder::pack_value(&mut Some(buf), self)
+            }
             Value::GeoJSON(ref val) => buf.write_geo(val),
         }
     }
@@ -402,6 +480,22 @@ impl<'a> From<&'a [u8]> for Value {
     }
 }
 
+/// Converts to a blob value. The buffer is taken over without copying if the `Bytes` value is the
+/// only reference to it.
+#[cfg(feature = "bytes")]
+impl From<Bytes> for Value {
+    fn from(val: Bytes) -> Value {
+        Value::Blob(Vec::from(val))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'a> From<&'a Bytes> for Value {
+    fn from(val: &'a Bytes) -> Value {
+        Value::Blob(val.to_vec())
+    }
+}
+
 impl From<bool> for Value {
     fn from(val: bool) -> Value {
         Value::Bool(val)
@@ -534,22 +628,276 @@ impl<'a> From<&'a bool> for Value {
     }
 }
 
-impl From<Value> for i64 {
-    fn from(val: Value) -> i64 {
+// Returns the error for a value that cannot be converted into the `expected` type.
+fn conversion_error(expected: &str, val: &Value) -> Error {
+    ErrorKind::ValueConversion(expected.to_string(), val.type_name().to_string()).into()
+}
+
+// Returns the error for an integer value that is out of range for the `expected` type.
+fn range_error<T: fmt::Display>(expected: &str, val: T) -> Error {
+    ErrorKind::ValueConversion(expected.to_string(), format!("int {}", val)).into()
+}
+
+macro_rules! try_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> TryFrom<&'a Value> for $t {
+                type Error = Error;
+
+                fn try_from(val: &'a Value) -> Result<$t> {
+                    match *val {
+                        Value::Int(v) => {
+                            <$t>::try_from(v).map_err(|_| range_error(stringify!($t), v))
+                        }
+                        Value::UInt(v) => {
+                            <$t>::try_from(v).map_err(|_| range_error(stringify!($t), v))
+                        }
+                        _ => Err(conversion_error(stringify!($t), val)),
+                    }
+                }
+            }
+
+            impl TryFrom<Value> for $t {
+                type Error = Error;
+
+                fn try_from(val: Value) -> Result<$t> {
+                    <$t>::try_from(&val)
+                }
+            }
+        )*
+    };
+}
+
+try_from_int!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+
+impl<'a> TryFrom<&'a Value> for f64 {
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<f64> {
+        match *val {
+            Value::Float(FloatValue::F64(v)) => Ok(f64::from_bits(v)),
+            Value::Float(FloatValue::F32(v)) => Ok(f32::from_bits(v).into()),
+            _ => Err(conversion_error("f64", val)),
+        }
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<f64> {
+        f64::try_from(&val)
+    }
+}
+
+/// The server stores all floating point values as 64-bit floats; converting such a value into
+/// `f32` may lose precision.
+impl<'a> TryFrom<&'a Value> for f32 {
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<f32> {
+        match *val {
+            Value::Float(FloatValue::F32(v)) => Ok(f32::from_bits(v)),
+            Value::Float(FloatValue::F64(v)) => Ok(f64::from_bits(v) as f32),
+            _ => Err(conversion_error("f32", val)),
+        }
+    }
+}
+
+impl TryFrom<Value> for f32 {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<f32> {
+        f32::try_from(&val)
+    }
+}
+
+/// Boolean bin values are stored as integers by older server versions; the integers `0` and `1`
+/// are converted to `false` and `true`.
+impl<'a> TryFrom<&'a Value> for bool {
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<bool> {
+        match *val {
+            Value::Bool(v) => Ok(v),
+            Value::Int(0) => Ok(false),
+            Value::Int(1) => Ok(true),
+            _ => Err(conversion_error("bool", val)),
+        }
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<bool> {
+        bool::try_from(&val)
+    }
+}
+
+/// Converts string and GeoJSON values.
+impl<'a> TryFrom<&'a Value> for String {
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<String> {
+        match *val {
+            Value::String(ref v) | Value::GeoJSON(ref v) => Ok(v.clone()),
+            _ => Err(conversion_error("String", val)),
+        }
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<String> {
+        match val {
+            Value::String(v) | Value::GeoJSON(v) => Ok(v),
+            val => Err(conversion_error("String", &val)),
+        }
+    }
+}
+
+/// Converts list values element by element. Blob and HLL values are converted byte by byte, so
+/// that they can be read as `Vec<u8>`.
+impl<'a, T> TryFrom<&'a Value> for Vec<T>
+where
+    T: TryFrom<&'a Value, Error = Error> + TryFrom<Value, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<Vec<T>> {
+        match *val {
+            Value::List(ref v) => v.iter().map(T::try_from).collect(),
+            Value::Blob(ref v) | Value::HLL(ref v) => {
+                v.iter().map(|b| T::try_from(Value::from(*b))).collect()
+            }
+            _ => Err(conversion_error("Vec", val)),
+        }
+    }
+}
+
+impl<T> TryFrom<Value> for Vec<T>
+where
+    T: TryFrom<Value, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<Vec<T>> {
+        match val {
+            Value::List(v) => v.into_iter().map(T::try_from).collect(),
+            Value::Blob(v) | Value::HLL(v) => {
+                v.into_iter().map(|b| T::try_from(Value::from(b))).collect()
+            }
+            val => Err(conversion_error("Vec", &val)),
+        }
+    }
+}
+
+/// Converts blob and HLL values. The buffer of the value is taken over without copying.
+///
+/// Reads are not zero-copy: a blob read from the server is copied out of the connection buffer
+/// into the `Vec<u8>` of `Value::Blob`, and only then handed to `Bytes`. The connection buffer is
+/// reused for the next command on the connection, so it cannot be shared with returned values,
+/// and `Value::Blob` keeps its `Vec<u8>` so that existing code matching on it keeps working. To
+/// read a blob without copying it, use `Client::get_with`, which borrows the value from the
+/// connection buffer as `ValueRef::Blob`.
+#[cfg(feature = "bytes")]
+impl TryFrom<Value> for Bytes {
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<Bytes> {
+        match val {
+            Value::Blob(v) | Value::HLL(v) => Ok(Bytes::from(v)),
+            val => Err(conversion_error("Bytes", &val)),
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl<'a> TryFrom<&'a Value> for Bytes {
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<Bytes> {
+        match *val {
+            Value::Blob(ref v) | Value::HLL(ref v) => Ok(Bytes::copy_from_slice(v)),
+            _ => Err(conversion_error("Bytes", val)),
+        }
+    }
+}
+
+/// Converts unordered and ordered map values entry by entry.
+impl<'a, K, V, S> TryFrom<&'a Value> for HashMap<K, V, S>
+where
+    K: TryFrom<&'a Value, Error = Error> + Eq + Hash,
+    V: TryFrom<&'a Value, Error = Error>,
+    S: BuildHasher + Default,
+{
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<HashMap<K, V, S>> {
+        match *val {
+            Value::HashMap(ref v) => v
+                .iter()
+                .map(|(k, v)| Ok((K::try_from(k)?, V::try_from(v)?)))
+                .collect(),
+            Value::OrderedMap(ref v) => v
+                .iter()
+                .map(|(k, v)| Ok((K::try_from(k)?, V::try_from(v)?)))
+                .collect(),
+            _ => Err(conversion_error("HashMap", val)),
+        }
+    }
+}
+
+impl<K, V, S> TryFrom<Value> for HashMap<K, V, S>
+where
+    K: TryFrom<Value, Error = Error> + Eq + Hash,
+    V: TryFrom<Value, Error = Error>,
+    S: BuildHasher + Default,
+{
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<HashMap<K, V, S>> {
         match val {
-            Value::Int(val) => val,
-            Value::UInt(val) => val as i64,
-            _ => panic!("Value is not an integer to convert."),
+            Value::HashMap(v) => v
+                .into_iter()
+                .map(|(k, v)| Ok((K::try_from(k)?, V::try_from(v)?)))
+                .collect(),
+            Value::OrderedMap(v) => v
+                .into_iter()
+                .map(|(k, v)| Ok((K::try_from(k)?, V::try_from(v)?)))
+                .collect(),
+            val => Err(conversion_error("HashMap", &val)),
         }
     }
 }
 
-impl<'a> From<&'a Value> for i64 {
-    fn from(val: &'a Value) -> i64 {
+/// Converts `Value::Nil` to `None`.
+impl<'a, T> TryFrom<&'a Value> for Option<T>
+where
+    T: TryFrom<&'a Value, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(val: &'a Value) -> Result<Option<T>> {
         match *val {
-            Value::Int(val) => val,
-            Value::UInt(val) => val as i64,
-            _ => panic!("Value is not an integer to convert."),
+            Value::Nil => Ok(None),
+            _ => T::try_from(val).map(Some),
+        }
+    }
+}
+
+impl<T> TryFrom<Value> for Option<T>
+where
+    T: TryFrom<Value, Error = Error>,
+{
+    type Error = Error;
+
+    fn try_from(val: Value) -> Result<Option<T>> {
+        match val {
+            Value::Nil => Ok(None),
+            val => T::try_from(val).map(Some),
         }
     }
 }
@@ -562,6 +910,10 @@ pub fn bytes_to_particle(ptype: u8, buf: &mut Buffer, len: usize) -> Result<Valu
             let val = buf.read_i64(None);
             Ok(Value::Int(val))
         }
+        ParticleType::BOOL => {
+            let val = buf.read_u8(None);
+            Ok(Value::Bool(val != 0))
+        }
         ParticleType::FLOAT => {
             let val = buf.read_f64(None);
             Ok(Value::Float(FloatValue::from(val)))
@@ -594,6 +946,112 @@ pub fn bytes_to_particle(ptype: u8, buf: &mut Buffer, len: usize) -> Result<Valu
     }
 }
 
+/// Bin value borrowed from the receive buffer of a connection, as returned by `RecordRef::bin`.
+///
+/// Strings and blobs reference the received bytes directly. Lists and maps are kept in their
+/// serialized form and are only decoded by `ValueRef::to_value`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueRef<'a> {
+    /// Empty value.
+    Nil,
+
+    /// Boolean value.
+    Bool(bool),
+
+    /// Integer value.
+    Int(i64),
+
+    /// Floating point value.
+    Float(f64),
+
+    /// String value.
+    String(&'a str),
+
+    /// Byte array value.
+    Blob(&'a [u8]),
+
+    /// Serialized list value.
+    List(&'a [u8]),
+
+    /// Serialized map value.
+    Map(&'a [u8]),
+
+    /// GeoJSON value.
+    GeoJSON(&'a str),
+
+    /// HyperLogLog value.
+    HLL(&'a [u8]),
+}
+
+impl<'a> ValueRef<'a> {
+    // Decodes a bin value without copying it out of the buffer.
+    pub(crate) fn from_particle(ptype: u8, bytes: &'a [u8]) -> Result<Self> {
+        let value = match ParticleType::from(ptype) {
+            ParticleType::NULL => ValueRef::Nil,
+            ParticleType::INTEGER => ValueRef::Int(NetworkEndian::read_i64(particle(bytes, 8)?)),
+            ParticleType::BOOL => ValueRef::Bool(particle(bytes, 1)?[0] != 0),
+            ParticleType::FLOAT => ValueRef::Float(NetworkEndian::read_f64(particle(bytes, 8)?)),
+            ParticleType::STRING => ValueRef::String(std::str::from_utf8(bytes)?),
+            ParticleType::GEOJSON => {
+                let ncells = NetworkEndian::read_i16(&particle(bytes, 3)?[1..]) as usize;
+                let header_size = 3 + ncells * 8;
+                particle(bytes, header_size)?;
+                ValueRef::GeoJSON(std::str::from_utf8(&bytes[header_size..])?)
+            }
+            ParticleType::BLOB => ValueRef::Blob(bytes),
+            ParticleType::LIST => ValueRef::List(bytes),
+            ParticleType::MAP => ValueRef::Map(bytes),
+            ParticleType::HLL => ValueRef::HLL(bytes),
+            ParticleType::DIGEST | ParticleType::LDT => bail!(ErrorKind::BadResponse(format!(
+                "Unsupported particle type: {}",
+                ptype
+            ))),
+        };
+        Ok(value)
+    }
+
+    /// Returns `true` if this is the empty value.
+    pub const fn is_nil(&self) -> bool {
+        matches!(*self, ValueRef::Nil)
+    }
+
+    /// Copies the value out of the buffer, decoding lists and maps.
+    pub fn to_value(&self) -> Result<Value> {
+        let value = match *self {
+            ValueRef::Nil => Value::Nil,
+            ValueRef::Bool(val) => Value::Bool(val),
+            ValueRef::Int(val) => Value::Int(val),
+            ValueRef::Float(val) => Value::Float(FloatValue::from(val)),
+            ValueRef::String(val) => Value::String(val.to_string()),
+            ValueRef::Blob(val) => Value::Blob(val.to_vec()),
+            ValueRef::GeoJSON(val) => Value::GeoJSON(val.to_string()),
+            ValueRef::HLL(val) => Value::HLL(val.to_vec()),
+            ValueRef::List(val) => decoder::unpack_value_list(&mut owned_buffer(val))?,
+            ValueRef::Map(val) => decoder::unpack_value_map(&mut owned_buffer(val))?,
+        };
+        Ok(value)
+    }
+}
+
+// Copies serialized bytes into a buffer for the msgpack decoder.
+fn owned_buffer(bytes: &[u8]) -> Buffer {
+    let mut buf = Buffer::new(0);
+    buf.data_buffer = bytes.to_vec();
+    buf
+}
+
+// Returns the particle bytes, after checking that they contain at least `len` bytes.
+fn particle(bytes: &[u8], len: usize) -> Result<&[u8]> {
+    if bytes.len() < len {
+        bail!(ErrorKind::BadResponse(format!(
+            "Truncated particle: expected {} bytes, got {}",
+            len,
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
 /// Constructs a new Value from one of the supported native data types.
 #[macro_export]
 macro_rules! as_val {
@@ -759,7 +1217,12 @@ impl Serialize for Value {
 
 #[cfg(test)]
 mod tests {
-    use super::Value;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    use super::{bytes_to_particle, Value};
+    use crate::commands::buffer::Buffer;
+    use crate::commands::ParticleType;
 
     #[test]
     fn as_string() {
@@ -784,6 +1247,145 @@ mod tests {
         assert_eq!(as_geo!(string), as_geo!(str));
     }
 
+    #[test]
+    fn bool_particle() {
+        let mut buf = Buffer::new(1024);
+        buf.data_buffer = vec![1];
+        let val = bytes_to_particle(ParticleType::BOOL as u8, &mut buf, 1).unwrap();
+        assert_eq!(val, Value::Bool(true));
+    }
+
+    #[test]
+    fn ordered_map_particle() {
+        // map with a K-ordered extension header, followed by 3 => 4, 1 => 2
+        let mut buf = Buffer::new(1024);
+        buf.data_buffer = vec![0x83, 0xc7, 0x00, 0x01, 0xc0, 0x03, 0x04, 0x01, 0x02];
+        let len = buf.data_buffer.len();
+        let val = bytes_to_particle(ParticleType::MAP as u8, &mut buf, len).unwrap();
+        assert_eq!(
+            val,
+            Value::OrderedMap(vec![
+                (Value::from(3u8), Value::from(4u8)),
+                (Value::from(1u8), Value::from(2u8))
+            ])
+        );
+
+        // maps without the header are decoded as unordered maps
+        let mut buf = Buffer::new(1024);
+        buf.data_buffer = vec![0x81, 0x01, 0x02];
+        let val = bytes_to_particle(ParticleType::MAP as u8, &mut buf, 3).unwrap();
+        assert_eq!(val, as_map!(1 => 2));
+
+        // range results in rank order, e.g. 2 => 1, 1 => 5
+        let mut buf = Buffer::new(1024);
+        buf.data_buffer = vec![0x83, 0xc7, 0x00, 0x08, 0xc0, 0x02, 0x01, 0x01, 0x05];
+        let len = buf.data_buffer.len();
+        let val = bytes_to_particle(ParticleType::MAP as u8, &mut buf, len).unwrap();
+        assert_eq!(
+            val,
+            Value::OrderedMap(vec![
+                (Value::from(2u8), Value::from(1u8)),
+                (Value::from(1u8), Value::from(5u8))
+            ])
+        );
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(i64::try_from(Value::from(-3)).unwrap(), -3);
+        assert_eq!(u8::try_from(&Value::from(255)).unwrap(), 255);
+        assert!(u8::try_from(&Value::from(256)).is_err());
+        assert!(u32::try_from(&Value::from(-1)).is_err());
+        assert_eq!(u64::try_from(Value::from(u64::MAX)).unwrap(), u64::MAX);
+        assert!(i64::try_from(Value::from(u64::MAX)).is_err());
+        assert!(i64::try_from(Value::from("1")).is_err());
+
+        assert_eq!(f64::try_from(Value::from(1.5)).unwrap(), 1.5);
+        assert_eq!(f32::try_from(Value::from(1.5f64)).unwrap(), 1.5f32);
+        assert!(f64::try_from(Value::from(1)).is_err());
+
+        assert!(bool::try_from(Value::from(true)).unwrap());
+        assert!(bool::try_from(Value::from(1)).unwrap());
+        assert!(bool::try_from(Value::from(2)).is_err());
+
+        assert_eq!(String::try_from(Value::from("abc")).unwrap(), "abc");
+        assert_eq!(String::try_from(&as_geo!("{}")).unwrap(), "{}");
+
+        assert_eq!(
+            Vec::<u8>::try_from(Value::from(vec![1u8, 2])).unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            Vec::<i32>::try_from(&as_list!(1, 2, 3)).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(Vec::<i32>::try_from(&as_list!(1, "2")).is_err());
+
+        let map: HashMap<String, i64> = HashMap::try_from(as_map!("a" => 1, "b" => 2)).unwrap();
+        assert_eq!(map["b"], 2);
+        let ordered = Value::OrderedMap(vec![(Value::from(1), Value::from("x"))]);
+        let map: HashMap<u8, String> = HashMap::try_from(&ordered).unwrap();
+        assert_eq!(map[&1], "x");
+
+        assert_eq!(Option::<i64>::try_from(Value::Nil).unwrap(), None);
+        assert_eq!(Option::<i64>::try_from(&Value::from(4)).unwrap(), Some(4));
+        let list: Vec<Option<i64>> = Vec::try_from(as_list!(1, Value::Nil)).unwrap();
+        assert_eq!(list, vec![Some(1), None]);
+
+        let err = i16::try_from(Value::from("x")).unwrap_err();
+        assert_eq!(err.to_string(), "Cannot convert string value to i16");
+        let err = i8::try_from(Value::from(1000)).unwrap_err();
+        assert_eq!(err.to_string(), "Cannot convert int 1000 value to i8");
+    }
+
+    #[test]
+    fn accessors() {
+        assert_eq!(Value::from(1).as_i64(), Some(1));
+        assert_eq!(Value::from("a").as_i64(), None);
+        assert_eq!(Value::from(0.5).as_f64(), Some(0.5));
+        assert_eq!(Value::from(false).as_bool(), Some(false));
+        assert_eq!(Value::from("a").as_str(), Some("a"));
+        assert_eq!(Value::from(vec![1u8]).as_bytes(), Some(&[1u8][..]));
+        assert_eq!(as_list!(1).as_list(), Some(&[Value::from(1)][..]));
+        assert_eq!(
+            as_map!("a" => 1).get(&Value::from("a")),
+            Some(&Value::from(1))
+        );
+        assert_eq!(Value::from(1).get(&Value::from("a")), None);
+    }
+
+    #[test]
+    fn ordered_map_write() {
+        let val = Value::OrderedMap(vec![
+            (Value::from(3), Value::from(4)),
+            (Value::from(1), Value::from(2)),
+        ]);
+        assert_eq!(val.particle_type() as u8, ParticleType::MAP as u8);
+
+        let mut buf = Buffer::new(1024);
+        buf.data_buffer = vec![0; val.estimate_size()];
+        val.write_to(&mut buf);
+        assert_eq!(buf.data_buffer, vec![0x82, 0x03, 0x04, 0x01, 0x02]);
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn bytes() {
+        use bytes::Bytes;
+
+        let payload = Bytes::from(vec![1u8, 2, 3]);
+        let ptr = payload.as_ptr();
+        let val = Value::from(payload);
+        assert_eq!(val, Value::from(vec![1u8, 2, 3]));
+        assert_eq!(val.as_bytes().unwrap().as_ptr(), ptr);
+
+        assert_eq!(Bytes::try_from(&val).unwrap(), &[1u8, 2, 3][..]);
+        let payload = Bytes::try_from(val).unwrap();
+        assert_eq!(payload.as_ptr(), ptr);
+        assert!(Bytes::try_from(Value::HLL(vec![0])).is_ok());
+        assert!(Bytes::try_from(Value::from("abc")).is_err());
+    }
+
     #[test]
     #[cfg(feature = "serialization")]
     fn serializer() {
@@ -13,16 +13,19 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::collections::HashMap;
 use std::str;
 use std::sync::Arc;
 use std::vec::Vec;
 
 use aerospike_core::errors::Result;
+use aerospike_core::operations::cdt_context::CdtContext;
 use aerospike_core::operations::Operation;
 use aerospike_core::{
-    BatchPolicy, BatchRead, Bin, Bins, ClientPolicy, CollectionIndexType, IndexTask, IndexType,
-    Key, Node, QueryPolicy, ReadPolicy, Record, Recordset, RegisterTask, ScanPolicy, Statement,
-    ToHosts, UDFLang, Value, WritePolicy,
+    AbortStatus, Aggregator, BatchPolicy, BatchRead, Bin, Bins, ClientPolicy, CollectionIndexType,
+    CommitStatus, IndexInfo, IndexTask, IndexType, Key, NamespaceInfo, Node, QueryPolicy,
+    ReadPolicy, Record, RecordRef, Recordset, RegisterTask, ScanPolicy, SetInfo, Statement,
+    ToHosts, Txn, UDFInfo, UDFLang, Update, UpdatePolicy, Value, WritePolicy,
 };
 use futures::executor::block_on;
 
@@ -168,6 +171,19 @@ impl Client {
         block_on(self.async_client.get(policy, key, bins))
     }
 
+    /// Read record for the specified key and pass it to `f` while it is still held in the receive
+    /// buffer of the connection, without decoding it into an owned `Record` first. Bins are
+    /// decoded only when accessed through the `RecordRef`, and string and blob values borrow the
+    /// received bytes, avoiding allocations for large values. Returns the result of `f`.
+    pub fn get_with<T, F, R>(&self, policy: &ReadPolicy, key: &Key, bins: T, f: F) -> Result<R>
+    where
+        T: Into<Bins> + Send + Sync + 'static,
+        F: FnOnce(RecordRef<'_>) -> R + Send,
+        R: Send,
+    {
+        block_on(self.async_client.get_with(policy, key, bins, f))
+    }
+
     /// Read multiple record for specified batch keys in one batch call. This method allows
     /// different namespaces/bins to be requested for each key in the batch. If the `BatchRead` key
     /// field is not found, the corresponding record field will be `None`. The policy can be used
@@ -362,6 +378,12 @@ impl Client {
         block_on(self.async_client.exists(policy, key))
     }
 
+    /// Determine if a record key exists, using a read policy. Unlike `exists`, the policy can be
+    /// used to specify the replica to read from and to request hedging.
+    pub fn exists_with_read_policy(&self, policy: &ReadPolicy, key: &Key) -> Result<bool> {
+        block_on(self.async_client.exists_with_read_policy(policy, key))
+    }
+
     /// Perform multiple read/write operations on a single key in one batch call.
     ///
     /// Operations on scalar values, lists and maps can be performed in the same call.
@@ -400,6 +422,114 @@ impl Client {
         block_on(self.async_client.operate(policy, key, ops))
     }
 
+    /// Read a record, modify it and write it back, using the record generation to detect
+    /// concurrent modifications. `f` is called with the current record, or `None` if the record
+    /// does not exist, and returns the modification to apply. If the record was modified, created
+    /// or deleted by another client in the meantime, the cycle is retried with the new record,
+    /// sleeping between retries as configured in the policy; `f` is therefore called once per
+    /// attempt and must not have side effects beyond computing the update.
+    ///
+    /// Existing records are written with `GenerationPolicy::ExpectGenEqual` and the generation of
+    /// the record that was read; new records are written with `RecordExistsAction::CreateOnly`.
+    /// All other fields of the policy's write policy, e.g. the expiration, apply unchanged.
+    ///
+    /// Returns whether the record was written or deleted; `false` if `f` returned
+    /// `Update::Abort`, or `Update::Delete` for a record that does not exist. Fails with
+    /// `ResultCode::GenerationError` or `ResultCode::KeyExistsError` if the record was still
+    /// modified concurrently after the last retry.
+    ///
+    /// # Examples
+    ///
+    /// Mark an order as shipped, unless it has not been paid yet.
+    ///
+    /// ```rust,edition2018
+    /// # use aerospike::*;
+    /// use std::collections::HashMap;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let key = as_key!("test", "orders", "order-1");
+    /// let result = client
+    ///     .update_with(&UpdatePolicy::default(), &key, |record| match record {
+    ///         Some(record) if record.bins.get("status") == Some(&Value::from("paid")) => {
+    ///             let mut bins = HashMap::new();
+    ///             bins.insert("status".to_string(), Value::from("shipped"));
+    ///             Update::Put(bins)
+    ///         }
+    ///         _ => Update::Abort,
+    ///     });
+    /// match result {
+    ///     Ok(true) => println!("Order shipped"),
+    ///     Ok(false) => println!("Order not paid"),
+    ///     Err(err) => println!("Error updating order: {}", err),
+    /// }
+    /// ```
+    pub fn update_with<F>(&self, policy: &UpdatePolicy, key: &Key, f: F) -> Result<bool>
+    where
+        F: FnMut(Option<Record>) -> Update + Send,
+    {
+        block_on(self.async_client.update_with(policy, key, f))
+    }
+
+    /// Start a multi-record transaction. Commands take part in the transaction when the returned
+    /// handle is set as the `txn` of their policy. The transaction must be completed with
+    /// `commit` or `abort`. Transactions require a strong consistency namespace.
+    ///
+    /// # Examples
+    ///
+    /// Transfer an amount between two accounts.
+    ///
+    /// ```rust,edition2018
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap();
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let txn = client.begin_transaction();
+    /// let mut policy = WritePolicy::default();
+    /// policy.base_policy.txn = Some(txn.clone());
+    ///
+    /// let from = as_key!("test", "accounts", "alice");
+    /// let to = as_key!("test", "accounts", "bob");
+    /// let res = client
+    ///     .add(&policy, &from, &[as_bin!("balance", -100)])
+    ///     .and_then(|_| client.add(&policy, &to, &[as_bin!("balance", 100)]));
+    ///
+    /// match res {
+    ///     Ok(()) => match client.commit(&WritePolicy::default(), &txn) {
+    ///         Ok(status) => println!("Transfer committed: {:?}", status),
+    ///         Err(err) => println!("Transfer failed: {}", err),
+    ///     },
+    ///     Err(err) => {
+    ///         println!("Transfer failed: {}", err);
+    ///         client.abort(&WritePolicy::default(), &txn).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn begin_transaction(&self) -> Txn {
+        self.async_client.begin_transaction()
+    }
+
+    /// Commit a multi-record transaction. The versions of all records read in the transaction
+    /// are verified first; if any of them was modified by another command, the transaction is
+    /// aborted and `ErrorKind::TxnCommit` with `CommitError::VerifyFail` is returned. Otherwise
+    /// the records written in the transaction are rolled forward. The policy applies to all
+    /// commands sent during the commit.
+    ///
+    /// If `ErrorKind::TxnCommit` reports the commit as in doubt, it is unknown whether the
+    /// transaction was committed; the commit can then be retried. A failure to roll forward some
+    /// records or to remove the monitor record after the commit point is reported in the returned
+    /// status; the server completes those steps once the transaction deadline has passed.
+    pub fn commit(&self, policy: &WritePolicy, txn: &Txn) -> Result<CommitStatus> {
+        block_on(self.async_client.commit(policy, txn))
+    }
+
+    /// Abort a multi-record transaction and roll back the records written in it. Fails with
+    /// `ResultCode::MrtCommitted` if the transaction was already committed. The policy applies to
+    /// all commands sent during the abort.
+    pub fn abort(&self, policy: &WritePolicy, txn: &Txn) -> Result<AbortStatus> {
+        block_on(self.async_client.abort(policy, txn))
+    }
+
     /// Register a package containing user-defined functions (UDF) with the cluster. This
     /// asynchronous server call will return before the command is complete. The client registers
     /// the UDF package with a single, random cluster node; from there a copy will get distributed
@@ -469,6 +599,31 @@ impl Client {
         )
     }
 
+    /// Register a package containing user-defined functions (UDF) with the cluster, unless the
+    /// cluster already has a package with the same name and content. Returns `None` if the
+    /// package was up to date and did not have to be registered.
+    pub fn register_udf_if_changed(
+        &self,
+        client_path: &str,
+        udf_name: &str,
+        language: UDFLang,
+    ) -> Result<Option<RegisterTask>> {
+        block_on(
+            self.async_client
+                .register_udf_if_changed(client_path, udf_name, language),
+        )
+    }
+
+    /// List the user-defined function (UDF) packages registered with the cluster.
+    pub fn list_udfs(&self) -> Result<Vec<UDFInfo>> {
+        block_on(self.async_client.list_udfs())
+    }
+
+    /// Download the source of a user-defined function (UDF) package from the cluster.
+    pub fn get_udf(&self, udf_name: &str) -> Result<Vec<u8>> {
+        block_on(self.async_client.get_udf(udf_name))
+    }
+
     /// Remove a user-defined function (UDF) module from the server.
     pub fn remove_udf(&self, udf_name: &str, language: UDFLang) -> Result<()> {
         block_on(self.async_client.remove_udf(udf_name, language))
@@ -537,6 +692,27 @@ impl Client {
         block_on(self.async_client.scan(policy, namespace, set_name, bins))
     }
 
+    /// Read all records in the specified namespace and set and aggregate them on the client. The
+    /// records of each node are folded into a partial result concurrently, as they arrive; the
+    /// partial results are then merged into the final result. See `Aggregator` for details.
+    pub fn scan_fold<T, A>(
+        &self,
+        policy: &ScanPolicy,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+        aggregator: &Aggregator<A>,
+    ) -> Result<A>
+    where
+        T: Into<Bins> + Send + Sync + 'static,
+        A: Send + 'static,
+    {
+        block_on(
+            self.async_client
+                .scan_fold(policy, namespace, set_name, bins, aggregator),
+        )
+    }
+
     /// Read all records in the specified namespace and set for one node only and return a record
     /// iterator. The scan executor puts records on a queue in separate threads. The calling thread
     /// concurrently pops records off the queue through the record iterator. Up to
@@ -591,6 +767,36 @@ impl Client {
         block_on(self.async_client.query(policy, statement))
     }
 
+    /// Execute a query on all server nodes and aggregate the records on the client. The records
+    /// of each node are folded into a partial result concurrently, as they arrive; the partial
+    /// results are then merged into the final result. See `Aggregator` for details.
+    pub fn query_fold<A: Send + 'static>(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        aggregator: &Aggregator<A>,
+    ) -> Result<A> {
+        block_on(self.async_client.query_fold(policy, statement, aggregator))
+    }
+
+    /// Execute a stream UDF aggregation query and return the aggregated values. The server nodes
+    /// run the stream up to and including its first `reduce` operation; the remaining stages are
+    /// run on the client, using the UDF package source given in `udf_body`.
+    ///
+    /// Requires the `lua` feature.
+    #[cfg(feature = "lua")]
+    pub fn query_aggregate(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        udf_body: &[u8],
+    ) -> Result<Vec<Value>> {
+        block_on(
+            self.async_client
+                .query_aggregate(policy, statement, udf_body),
+        )
+    }
+
     /// Execute a query on a single server node and return a record iterator. The query executor
     /// puts records on a queue in separate threads. The calling thread concurrently pops records
     /// off the queue through the record iterator.
@@ -681,6 +887,31 @@ impl Client {
         ))
     }
 
+    /// Create a complex secondary index on a nested list or map element of a bin. The element is
+    /// identified by the context path `ctx`. This asynchronous server call returns before the
+    /// command is complete.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_complex_index_with_context(
+        &self,
+        namespace: &str,
+        set_name: &str,
+        bin_name: &str,
+        index_name: &str,
+        index_type: IndexType,
+        collection_index_type: CollectionIndexType,
+        ctx: &[CdtContext],
+    ) -> Result<()> {
+        block_on(self.async_client.create_complex_index_with_context(
+            namespace,
+            set_name,
+            bin_name,
+            index_name,
+            index_type,
+            collection_index_type,
+            ctx,
+        ))
+    }
+
     /// Delete secondary index.
     pub fn drop_index(&self, namespace: &str, set_name: &str, index_name: &str) -> Result<()> {
         block_on(
@@ -688,4 +919,51 @@ impl Client {
                 .drop_index(namespace, set_name, index_name),
         )
     }
+
+    /// List the secondary indexes of a namespace, including their load progress and number of
+    /// entries across all nodes of the cluster.
+    pub fn list_indexes(&self, namespace: &str) -> Result<Vec<IndexInfo>> {
+        block_on(self.async_client.list_indexes(namespace))
+    }
+
+    /// Sends the info commands to every active cluster node concurrently and returns the
+    /// responses, keyed by node name. If `require_agreement` is set, the call fails unless all
+    /// nodes respond and all responses are identical.
+    pub fn info_all(
+        &self,
+        commands: &[&str],
+        require_agreement: bool,
+    ) -> Result<HashMap<String, Result<HashMap<String, String>>>> {
+        block_on(self.async_client.info_all(commands, require_agreement))
+    }
+
+    /// Returns the names of the namespaces configured on any of the cluster nodes.
+    pub fn namespaces(&self) -> Result<Vec<String>> {
+        block_on(self.async_client.namespaces())
+    }
+
+    /// Returns the configuration and statistics of a namespace, keyed by node name.
+    pub fn namespace_info(&self, namespace: &str) -> Result<HashMap<String, NamespaceInfo>> {
+        block_on(self.async_client.namespace_info(namespace))
+    }
+
+    /// Returns the statistics of all sets in a namespace, aggregated across all cluster nodes.
+    pub fn sets(&self, namespace: &str) -> Result<Vec<SetInfo>> {
+        block_on(self.async_client.sets(namespace))
+    }
+
+    /// Returns the bin names used in a namespace on any of the cluster nodes.
+    pub fn bins(&self, namespace: &str) -> Result<Vec<String>> {
+        block_on(self.async_client.bins(namespace))
+    }
+
+    /// Returns the statistics of each cluster node, keyed by node name.
+    pub fn node_statistics(&self) -> Result<HashMap<String, HashMap<String, String>>> {
+        block_on(self.async_client.node_statistics())
+    }
+
+    /// Returns the server build version of each cluster node, keyed by node name.
+    pub fn build_versions(&self) -> Result<HashMap<String, String>> {
+        block_on(self.async_client.build_versions())
+    }
 }
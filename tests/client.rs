@@ -56,6 +56,52 @@ async fn get_node() {
     client.close().await.unwrap();
 }
 
+#[aerospike_macro::test]
+async fn cluster_info() {
+    let client = common::client().await;
+    let namespace = common::namespace();
+    let node_count = client.nodes().await.len();
+
+    let namespaces = client.namespaces().await.unwrap();
+    assert!(namespaces.iter().any(|ns| ns == namespace));
+
+    let infos = client.namespace_info(namespace).await.unwrap();
+    assert_eq!(infos.len(), node_count);
+    assert!(infos.values().all(|info| info.name == namespace));
+
+    client.sets(namespace).await.unwrap();
+    client.bins(namespace).await.unwrap();
+
+    let stats = client.node_statistics().await.unwrap();
+    assert_eq!(stats.len(), node_count);
+
+    let versions = client.build_versions().await.unwrap();
+    assert_eq!(versions.len(), node_count);
+    assert!(versions.values().all(|version| !version.is_empty()));
+
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn info_all() {
+    let client = common::client().await;
+    let node_names = client.node_names().await;
+
+    let responses = client.info_all(&["build", "node"], false).await.unwrap();
+    assert_eq!(responses.len(), node_names.len());
+    for name in &node_names {
+        let response = responses.get(name).unwrap().as_ref().unwrap();
+        assert_eq!(response.get("node"), Some(name));
+    }
+
+    // node names are unique, so the nodes never agree on them
+    if node_names.len() > 1 {
+        assert!(client.info_all(&["node"], true).await.is_err());
+    }
+
+    client.close().await.unwrap();
+}
+
 #[aerospike_macro::test]
 async fn close() {
     let client = Client::new(common::client_policy(), &common::hosts())
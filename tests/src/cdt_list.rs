@@ -366,6 +366,10 @@ fn cdt_list() {
     let rec = client.operate(&wpolicy, &key, ops).await.unwrap();
     assert_eq!(*rec.bins.get("bin").unwrap(), Value::from(1));
 
+    let ops = &vec![lists::get_by_value("bin", &val, ListReturnType::Exists)];
+    let rec = client.operate(&wpolicy, &key, ops).await.unwrap();
+    assert_eq!(rec.bins[0].1, Value::from(true));
+
     let val = vec![Value::from(1), Value::from("0")];
     let ops = &vec![lists::get_by_value_list("bin", &val, ListReturnType::Count)];
     let rec = client.operate(&wpolicy, &key, ops).await.unwrap();
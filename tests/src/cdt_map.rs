@@ -21,8 +21,8 @@ use crate::common;
 use aerospike::operations::cdt_context::{ctx_map_key, ctx_map_key_create};
 use aerospike::operations::{maps, MapOrder};
 use aerospike::{
-    as_bin, as_key, as_list, as_map, as_val, Bins, MapPolicy, MapReturnType, ReadPolicy,
-    WritePolicy,
+    as_bin, as_key, as_list, as_map, as_val, Bins, MapPolicy, MapReturnType, MapWriteMode,
+    ReadPolicy, Value, WritePolicy,
 };
 
 #[aerospike_macro::test]
@@ -153,6 +153,17 @@ async fn map_operations() {
     let rec = client.operate(&wpolicy, &key, &[op]).await.unwrap();
     assert_eq!(*rec.bins.get(bin_name).unwrap(), as_val!(2));
 
+    let op = maps::get_by_key(bin_name, &mkey, MapReturnType::Exists);
+    let rec = client.operate(&wpolicy, &key, &[op]).await.unwrap();
+    assert_eq!(rec.bins[0].1, as_val!(true));
+
+    let op = maps::get_by_key(bin_name, &mkey, MapReturnType::OrderedMap);
+    let rec = client.operate(&wpolicy, &key, &[op]).await.unwrap();
+    assert_eq!(
+        rec.bins[0].1,
+        Value::OrderedMap(vec![(as_val!("b"), as_val!(2))])
+    );
+
     let mkey = as_val!("b");
     let mkey2 = as_val!("d");
     let op = maps::get_by_key_range(bin_name, &mkey, &mkey2, MapReturnType::Count);
@@ -324,5 +335,76 @@ async fn map_operations() {
     let rec = client.operate(&wpolicy, &key, &op).await.unwrap();
     assert_eq!(*rec.bins.get(bin_name).unwrap(), as_val!(9));
 
+    let mkey = as_val!("ctxtest4");
+    let ctx = &vec![ctx_map_key(mkey)];
+    let op = [maps::create(bin_name, MapOrder::KeyOrdered, false, ctx)];
+    client.operate(&wpolicy, &key, &op).await.unwrap();
+    let op = [maps::size(bin_name).set_context(ctx)];
+    let rec = client.operate(&wpolicy, &key, &op).await.unwrap();
+    assert_eq!(rec.bins[0].1, as_val!(0));
+
+    let op = [maps::create(bin_name, MapOrder::KeyOrdered, true, &[])];
+    client.operate(&wpolicy, &key, &op).await.unwrap();
+
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn ordered_map_results() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let rpolicy = ReadPolicy::default();
+    let mpolicy = MapPolicy::new(MapOrder::KeyOrdered, MapWriteMode::Update);
+
+    let key = as_key!(namespace, set_name, common::rand_str(10));
+    let bin_name = "scores";
+
+    let mut items = HashMap::new();
+    items.insert(as_val!("d"), as_val!(40));
+    items.insert(as_val!("a"), as_val!(10));
+    items.insert(as_val!("c"), as_val!(30));
+    items.insert(as_val!("b"), as_val!(20));
+    let op = maps::put_items(&mpolicy, bin_name, &items);
+    client.operate(&wpolicy, &key, &[op]).await.unwrap();
+
+    // key ordered maps are read in key order
+    let rec = client.get(&rpolicy, &key, Bins::All).await.unwrap();
+    assert_eq!(
+        *rec.bins.get(bin_name).unwrap(),
+        Value::OrderedMap(vec![
+            (as_val!("a"), as_val!(10)),
+            (as_val!("b"), as_val!(20)),
+            (as_val!("c"), as_val!(30)),
+            (as_val!("d"), as_val!(40)),
+        ])
+    );
+
+    let (beg, end) = (as_val!("b"), as_val!("d"));
+    let op = maps::get_by_key_range(bin_name, &beg, &end, MapReturnType::KeyValue);
+    let rec = client.operate(&wpolicy, &key, &[op]).await.unwrap();
+    assert_eq!(
+        rec.bins[0].1,
+        Value::OrderedMap(vec![
+            (as_val!("b"), as_val!(20)),
+            (as_val!("c"), as_val!(30))
+        ])
+    );
+
+    // ordered maps can be written back
+    let val = rec.bins[0].1.clone();
+    client
+        .put(&wpolicy, &key, &[as_bin!("copy", val)])
+        .await
+        .unwrap();
+    let op = maps::size("copy");
+    let rec = client.operate(&wpolicy, &key, &[op]).await.unwrap();
+    assert_eq!(rec.bins[0].1, as_val!(2));
+
+    client.delete(&wpolicy, &key).await.unwrap();
     client.close().await.unwrap();
 }
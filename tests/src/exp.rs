@@ -19,6 +19,7 @@ use aerospike::expressions::*;
 use aerospike::ParticleType;
 use aerospike::*;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 const EXPECTED: usize = 100;
 
@@ -541,6 +542,23 @@ fn expression_rec_ops() {
     let count = count_results(rs);
     assert_eq!(count, 100, "SINCE UPDATE Test Failed");
 
+    let hour = Duration::from_secs(60 * 60);
+    let rs = test_filter(
+        &client,
+        and(vec![
+            gt(last_update(), time_val(SystemTime::now() - hour)),
+            lt(last_update(), time_val(SystemTime::now() + hour)),
+        ]),
+        &set_name,
+    )
+    .await;
+    let count = count_results(rs);
+    assert_eq!(count, 100, "LAST UPDATE TIME Test Failed");
+
+    let rs = test_filter(&client, lt(since_update(), millis_val(hour)), &set_name).await;
+    let count = count_results(rs);
+    assert_eq!(count, 100, "SINCE UPDATE DURATION Test Failed");
+
     // Records dont expire
     let rs = test_filter(&client, ge(void_time(), int_val(0)), &set_name).await;
     let count = count_results(rs);
@@ -68,3 +68,32 @@ async fn recreate_index() {
 
     client.close().await.unwrap();
 }
+
+#[aerospike_macro::test]
+async fn list_indexes() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let ns = common::namespace();
+    let set = create_test_set(&client, EXPECTED).await;
+    let bin = "bin";
+    let index = format!("{}_{}_{}", ns, set, bin);
+
+    let task = client
+        .create_index(ns, &set, bin, &index, IndexType::Numeric)
+        .await
+        .expect("Failed to create index");
+    task.wait_till_complete(None).await.unwrap();
+    assert_eq!(task.load_progress().await.unwrap(), 100);
+
+    let indexes = client.list_indexes(ns).await.unwrap();
+    let info = indexes.iter().find(|info| info.name == index).unwrap();
+    assert_eq!(info.set_name.as_deref(), Some(set.as_str()));
+    assert_eq!(info.bin_name, bin);
+    assert_eq!(info.index_type, IndexType::Numeric);
+    assert_eq!(info.load_pct, 100);
+    assert_eq!(info.entries, EXPECTED as u64);
+
+    client.drop_index(ns, &set, &index).await.unwrap();
+    client.close().await.unwrap();
+}
@@ -14,8 +14,11 @@
 // the License.
 use aerospike::operations;
 use aerospike::{
-    as_bin, as_blob, as_geo, as_key, as_list, as_map, as_val, Bins, ReadPolicy, Value, WritePolicy,
+    as_bin, as_blob, as_geo, as_key, as_list, as_map, as_val, Bins, Key, ReadPolicy, Record,
+    ScanPolicy, Update, UpdatePolicy, Value, ValueRef, WritePolicy,
 };
+use futures::future::join_all;
+use std::time::Duration;
 
 
 use crate::common;
@@ -100,3 +103,150 @@ async fn connect() {
 
     client.close().await.unwrap();
 }
+
+#[aerospike_macro::test]
+async fn u64_keys() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let mut wpolicy = WritePolicy::default();
+    wpolicy.send_key = true;
+
+    let key = Key::with_user_key(namespace, set_name.as_str(), u64::max_value());
+    client
+        .put(&wpolicy, &key, &[as_bin!("bin", 1)])
+        .await
+        .unwrap();
+
+    // u64 keys are stored as the signed integer with the same bit pattern
+    let signed = as_key!(namespace, set_name, -1);
+    assert!(client
+        .exists(&WritePolicy::default(), &signed)
+        .await
+        .unwrap());
+
+    let rs = client
+        .scan(&ScanPolicy::default(), namespace, set_name, Bins::None)
+        .await
+        .unwrap();
+    let keys: Vec<_> = (&*rs).map(|record| record.unwrap().key.unwrap()).collect();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(
+        keys[0].user_key_value().and_then(|key| key.as_u64()),
+        Some(u64::max_value())
+    );
+
+    client.delete(&wpolicy, &key).await.unwrap();
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn get_with() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let policy = ReadPolicy::default();
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, "get_with");
+
+    let bins = [
+        as_bin!("blob", as_blob!(vec![7u8; 1024])),
+        as_bin!("str", "hello"),
+        as_bin!("list", as_list!(1, 2, 3)),
+    ];
+    client.put(&wpolicy, &key, &bins).await.unwrap();
+
+    let len = client
+        .get_with(&policy, &key, Bins::All, |record| {
+            match record.bin("blob").unwrap() {
+                Some(ValueRef::Blob(bytes)) => bytes.len(),
+                _ => 0,
+            }
+        })
+        .await
+        .unwrap();
+    assert_eq!(len, 1024);
+
+    let (s, list) = client
+        .get_with(&policy, &key, ["str", "list"], |record| {
+            let s = match record.bin("str").unwrap() {
+                Some(ValueRef::String(s)) => s.to_string(),
+                _ => String::new(),
+            };
+            (s, record.bin("list").unwrap().unwrap().to_value().unwrap())
+        })
+        .await
+        .unwrap();
+    assert_eq!(s, "hello");
+    assert_eq!(list, as_list!(1, 2, 3));
+
+    let record = client
+        .get_with(&policy, &key, Bins::All, |record| record.to_record())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.bins.len(), 3);
+
+    client.delete(&wpolicy, &key).await.unwrap();
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn update_with() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, "update_with");
+    let policy = UpdatePolicy {
+        max_retries: 100,
+        sleep_between_retries: Some(Duration::from_millis(1)),
+        ..UpdatePolicy::default()
+    };
+
+    let increment = |record: Option<Record>| {
+        let mut bins = record.map(|record| record.bins).unwrap_or_default();
+        let count = bins.get("count").and_then(Value::as_i64).unwrap_or(0);
+        bins.insert("count".to_string(), Value::from(count + 1));
+        Update::Put(bins)
+    };
+
+    // concurrent updates of the same record, including its creation, all succeed
+    let updates = (0..10).map(|_| client.update_with(&policy, &key, increment));
+    for result in join_all(updates).await {
+        assert!(result.unwrap());
+    }
+    let record = client
+        .get(&ReadPolicy::default(), &key, Bins::All)
+        .await
+        .unwrap();
+    assert_eq!(record.bins.get("count"), Some(&Value::from(10)));
+    assert_eq!(record.generation, 10);
+
+    let updated = client
+        .update_with(&policy, &key, |_| Update::Abort)
+        .await
+        .unwrap();
+    assert!(!updated);
+
+    let deleted = client
+        .update_with(&policy, &key, |_| Update::Delete)
+        .await
+        .unwrap();
+    assert!(deleted);
+    let deleted = client
+        .update_with(&policy, &key, |record| {
+            assert!(record.is_none());
+            Update::Delete
+        })
+        .await
+        .unwrap();
+    assert!(!deleted);
+
+    client.close().await.unwrap();
+}
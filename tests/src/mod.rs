@@ -32,4 +32,5 @@ mod scan;
 mod serialization;
 mod task;
 mod truncate;
+mod txn;
 mod udf;
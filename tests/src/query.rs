@@ -87,7 +87,7 @@ async fn query_single_consumer() {
         match res {
             Ok(rec) => {
                 count += 1;
-                let v: i64 = rec.bins["bin"].clone().into();
+                let v: i64 = rec.get("bin").unwrap();
                 assert!(v >= 0);
                 assert!(v < 10);
             }
@@ -99,6 +99,51 @@ async fn query_single_consumer() {
     client.close().await.unwrap();
 }
 
+#[aerospike_macro::test]
+async fn query_nested_context() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace = common::namespace();
+    let set_name = common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+    let qpolicy = QueryPolicy::default();
+
+    for i in 0..10 as i64 {
+        let key = as_key!(namespace, &set_name, i);
+        let wbin = as_bin!("bin", as_map!("address" => as_map!("zip" => i)));
+        client.put(&wpolicy, &key, &[wbin]).await.unwrap();
+    }
+
+    let ctx = vec![operations::cdt_context::ctx_map_key(as_val!("address"))];
+    let index_name = format!("{}_{}_{}", namespace, set_name, "zip");
+    client
+        .create_complex_index_with_context(
+            namespace,
+            &set_name,
+            "bin",
+            &index_name,
+            IndexType::Numeric,
+            CollectionIndexType::MapValues,
+            &ctx,
+        )
+        .await
+        .expect("Failed to create index");
+    thread::sleep(std::time::Duration::from_millis(1000));
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_contains!("bin", 3, CollectionIndexType::MapValues).set_context(&ctx));
+    let rs = client.query(&qpolicy, statement).await.unwrap();
+    let mut count = 0;
+    for res in &*rs {
+        res.unwrap();
+        count += 1;
+    }
+    assert_eq!(count, 1);
+
+    client.close().await.unwrap();
+}
+
 #[aerospike_macro::test]
 async fn query_nobins() {
     let _ = env_logger::try_init();
@@ -153,7 +198,7 @@ async fn query_multi_consumer() {
                 match res {
                     Ok(rec) => {
                         count.fetch_add(1, Ordering::Relaxed);
-                        let v: i64 = rec.bins["bin"].clone().into();
+                        let v: i64 = rec.get("bin").unwrap();
                         assert!(v >= 0);
                         assert!(v < 10);
                     }
@@ -205,3 +250,90 @@ async fn query_node() {
 
     client.close().await.unwrap();
 }
+
+#[cfg(feature = "lua")]
+#[aerospike_macro::test]
+async fn query_aggregate() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace = common::namespace();
+    let set_name = create_test_set(&client, EXPECTED).await;
+    let qpolicy = QueryPolicy::default();
+
+    let udf_body = r#"
+local function add(a, b)
+  return a + b
+end
+
+function sum_bins(stream, min)
+  local function above(rec)
+    return rec['bin'] >= min
+  end
+  local function value(rec)
+    return rec['bin']
+  end
+  return stream : filter(above) : map(value) : reduce(add)
+end
+"#;
+    let task = client
+        .register_udf(udf_body.as_bytes(), "test_aggregate.lua", UDFLang::Lua)
+        .await
+        .unwrap();
+    task.wait_till_complete(None).await.unwrap();
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 99));
+    statement.set_aggregate_function("test_aggregate", "sum_bins", Some(&[as_val!(10)]));
+    let values = client
+        .query_aggregate(&qpolicy, statement, udf_body.as_bytes())
+        .await
+        .unwrap();
+    assert_eq!(values, vec![as_val!((10..100).sum::<i64>())]);
+
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn query_geo_typed() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace = common::namespace();
+    let set_name = common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+
+    for i in 0..10 {
+        let key = as_key!(namespace, &set_name, i);
+        let point = GeoPoint::new(-122.0 + f64::from(i) * 0.01, 37.5).unwrap();
+        client
+            .put(&wpolicy, &key, &[as_bin!("loc", point)])
+            .await
+            .unwrap();
+    }
+
+    let task = client
+        .create_index(
+            namespace,
+            &set_name,
+            "loc",
+            &format!("{}_{}_loc", namespace, set_name),
+            IndexType::Geo2DSphere,
+        )
+        .await
+        .expect("Failed to create index");
+    task.wait_till_complete(None).await.unwrap();
+
+    // roughly 3.3km around the first point, i.e. the first four points
+    let circle = GeoCircle::new(GeoPoint::new(-122.0, 37.5).unwrap(), 3_300.0).unwrap();
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_within_region!("loc", circle));
+    let rs = client
+        .query(&QueryPolicy::default(), statement)
+        .await
+        .unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert_eq!(count, 4);
+
+    client.close().await.unwrap();
+}
@@ -130,3 +130,37 @@ async fn scan_node() {
 
     client.close().await.unwrap();
 }
+
+#[aerospike_macro::test]
+async fn scan_fold() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace = common::namespace();
+    let set_name = create_test_set(&client, EXPECTED).await;
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.record_queue_size = 16;
+    let sum_by_parity = Aggregator::new(
+        || 0,
+        |sum, record| match record.bins.get("bin") {
+            Some(Value::Int(val)) => sum + val,
+            _ => sum,
+        },
+        |a, b| a + b,
+    )
+    .group_by(|record| matches!(record.bins["bin"], Value::Int(val) if val % 2 == 0));
+    let sums = client
+        .scan_fold(&spolicy, namespace, &set_name, Bins::All, &sum_by_parity)
+        .await
+        .unwrap();
+
+    let expected: i64 = (0..EXPECTED as i64).sum();
+    assert_eq!(sums[&true] + sums[&false], expected);
+    assert_eq!(
+        sums[&true],
+        (0..EXPECTED as i64).filter(|i| i % 2 == 0).sum::<i64>()
+    );
+
+    client.close().await.unwrap();
+}
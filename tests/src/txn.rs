@@ -0,0 +1,202 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+// Transactions require a strong consistency namespace.
+
+use aerospike::errors::ErrorKind;
+use aerospike::{
+    as_bin, as_key, AbortStatus, BatchPolicy, BatchRead, Bins, CommitError, CommitStatus,
+    QueryPolicy, ReadPolicy, ScanPolicy, Statement, TxnState, Value, WritePolicy,
+};
+
+use crate::common;
+
+#[aerospike_macro::test]
+async fn txn_commit() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key1 = as_key!(namespace, set_name, "txn_commit1");
+    let key2 = as_key!(namespace, set_name, "txn_commit2");
+    let wpolicy = WritePolicy::default();
+    client
+        .put(&wpolicy, &key1, &[as_bin!("bin", 1)])
+        .await
+        .unwrap();
+
+    let txn = client.begin_transaction();
+    let mut txn_wpolicy = WritePolicy::default();
+    txn_wpolicy.base_policy.txn = Some(txn.clone());
+    let mut txn_rpolicy = ReadPolicy::default();
+    txn_rpolicy.base_policy.txn = Some(txn.clone());
+
+    let record = client.get(&txn_rpolicy, &key1, Bins::All).await.unwrap();
+    assert_eq!(record.bins.get("bin"), Some(&Value::from(1)));
+    client
+        .put(&txn_wpolicy, &key2, &[as_bin!("bin", 2)])
+        .await
+        .unwrap();
+    assert_eq!(txn.namespace().as_deref(), Some(namespace));
+
+    // commands on other namespaces are rejected
+    let other = as_key!("other", set_name, "txn_commit");
+    assert!(client.get(&txn_rpolicy, &other, Bins::All).await.is_err());
+
+    let status = client.commit(&wpolicy, &txn).await.unwrap();
+    assert_eq!(status, CommitStatus::Ok);
+    assert_eq!(txn.state(), TxnState::Committed);
+    let status = client.commit(&wpolicy, &txn).await.unwrap();
+    assert_eq!(status, CommitStatus::AlreadyCommitted);
+    assert!(client.abort(&wpolicy, &txn).await.is_err());
+
+    let record = client
+        .get(&ReadPolicy::default(), &key2, Bins::All)
+        .await
+        .unwrap();
+    assert_eq!(record.bins.get("bin"), Some(&Value::from(2)));
+
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn txn_abort() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key = as_key!(namespace, set_name, "txn_abort");
+    let wpolicy = WritePolicy::default();
+    client
+        .put(&wpolicy, &key, &[as_bin!("bin", 1)])
+        .await
+        .unwrap();
+
+    let txn = client.begin_transaction();
+    let mut txn_wpolicy = WritePolicy::default();
+    txn_wpolicy.base_policy.txn = Some(txn.clone());
+    client
+        .put(&txn_wpolicy, &key, &[as_bin!("bin", 2)])
+        .await
+        .unwrap();
+    client.delete(&txn_wpolicy, &key).await.unwrap();
+
+    let status = client.abort(&wpolicy, &txn).await.unwrap();
+    assert_eq!(status, AbortStatus::Ok);
+    let status = client.abort(&wpolicy, &txn).await.unwrap();
+    assert_eq!(status, AbortStatus::AlreadyAborted);
+
+    // the transaction is closed for further commands
+    assert!(client
+        .put(&txn_wpolicy, &key, &[as_bin!("bin", 3)])
+        .await
+        .is_err());
+
+    let record = client
+        .get(&ReadPolicy::default(), &key, Bins::All)
+        .await
+        .unwrap();
+    assert_eq!(record.bins.get("bin"), Some(&Value::from(1)));
+
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn txn_verify_fail() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let key1 = as_key!(namespace, set_name, "txn_verify_fail1");
+    let key2 = as_key!(namespace, set_name, "txn_verify_fail2");
+    let wpolicy = WritePolicy::default();
+    client
+        .put(&wpolicy, &key1, &[as_bin!("bin", 1)])
+        .await
+        .unwrap();
+
+    let txn = client.begin_transaction();
+    let mut txn_wpolicy = WritePolicy::default();
+    txn_wpolicy.base_policy.txn = Some(txn.clone());
+    let mut txn_rpolicy = ReadPolicy::default();
+    txn_rpolicy.base_policy.txn = Some(txn.clone());
+
+    client.get(&txn_rpolicy, &key1, Bins::All).await.unwrap();
+    client
+        .put(&txn_wpolicy, &key2, &[as_bin!("bin", 2)])
+        .await
+        .unwrap();
+
+    // modify the record read in the transaction outside of it
+    client
+        .put(&wpolicy, &key1, &[as_bin!("bin", 3)])
+        .await
+        .unwrap();
+
+    match client.commit(&wpolicy, &txn).await {
+        Err(err) => match err.kind() {
+            ErrorKind::TxnCommit(err, in_doubt) => {
+                assert_eq!(*err, CommitError::VerifyFail);
+                assert!(!in_doubt);
+            }
+            kind => panic!("unexpected error: {:?}", kind),
+        },
+        Ok(status) => panic!("unexpected commit: {:?}", status),
+    }
+    assert_eq!(txn.state(), TxnState::Aborted);
+
+    // the write was rolled back
+    assert!(!client.exists(&WritePolicy::default(), &key2).await.unwrap());
+
+    client.close().await.unwrap();
+}
+
+#[aerospike_macro::test]
+async fn txn_unsupported_commands() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let txn = client.begin_transaction();
+
+    let mut bpolicy = BatchPolicy::default();
+    bpolicy.base_policy.txn = Some(txn.clone());
+    let batch = vec![BatchRead::new(as_key!(namespace, set_name, 1), Bins::All)];
+    match client.batch_get(&bpolicy, batch).await {
+        Err(err) => assert!(matches!(err.kind(), ErrorKind::InvalidArgument(_))),
+        Ok(_) => panic!("batch in a transaction"),
+    }
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.base_policy.txn = Some(txn.clone());
+    match client.scan(&spolicy, namespace, set_name, Bins::All).await {
+        Err(err) => assert!(matches!(err.kind(), ErrorKind::InvalidArgument(_))),
+        Ok(_) => panic!("scan in a transaction"),
+    }
+
+    let mut qpolicy = QueryPolicy::default();
+    qpolicy.base_policy.txn = Some(txn.clone());
+    let statement = Statement::new(namespace, set_name, Bins::All);
+    match client.query(&qpolicy, statement).await {
+        Err(err) => assert!(matches!(err.kind(), ErrorKind::InvalidArgument(_))),
+        Ok(_) => panic!("query in a transaction"),
+    }
+
+    client.close().await.unwrap();
+}
@@ -94,3 +94,35 @@ end
 
     client.close().await.unwrap();
 }
+
+#[aerospike_macro::test]
+async fn list_and_get_udfs() {
+    let _ = env_logger::try_init();
+
+    let client = common::client().await;
+
+    let udf_body = r#"
+function echo(rec, val)
+  return val
+end
+"#;
+
+    let task = client
+        .register_udf(udf_body.as_bytes(), "test_udf_list.lua", UDFLang::Lua)
+        .await
+        .unwrap();
+    task.wait_till_complete(None).await.unwrap();
+
+    let udfs = client.list_udfs().await.unwrap();
+    let udf = udfs
+        .iter()
+        .find(|udf| udf.package_name == "test_udf_list.lua")
+        .unwrap();
+    assert_eq!(udf.language, UDFLang::Lua);
+    assert_eq!(udf.hash.len(), 40);
+
+    let content = client.get_udf("test_udf_list.lua").await.unwrap();
+    assert_eq!(content, udf_body.as_bytes());
+
+    client.close().await.unwrap();
+}